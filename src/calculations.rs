@@ -2,11 +2,23 @@
 //!
 //! This is the common entry point for both rust and wasm library.
 
+use crate::units::dimension;
+use crate::units::temperature::Celsius;
+use crate::units::Kilograms;
 use wasm_bindgen::prelude::*;
 
+pub mod mash;
+pub mod water;
+
 /// Specific gravity to alchol (ABV) coefficient
 const GRAVITY_TO_ALCOHOL_COEFF: f32 = 131.25;
 
+/// Specific heat of water $c_w$ \[kJ / (kg K)\]
+pub const WATER_SPECIFIC_HEAT: f32 = 4.184;
+
+/// Typical specific heat of malted grain $c_g$ \[kJ / (kg K)\]
+pub const GRAIN_SPECIFIC_HEAT: f32 = 1.72;
+
 /// Alcohol (ABV) from gravity difference.
 ///
 /// Estimates the alcoholic concentration of the beer,
@@ -44,9 +56,89 @@ pub fn strike_water_volume(grain_weight: f32, mash_thickness: f32) -> f32 {
     grain_weight * mash_thickness
 }
 
+/// Mash tun that absorbs heat from the strike water
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MashTun {
+    /// Heat capacity of the tun $C_{tun}$ \[kJ / K\]
+    pub thermal_mass: f32,
+    /// Tun temperature $T_{tun}$ \[°C\] when the strike water is added.
+    ///
+    /// A tun preheated to the target mash temperature does not absorb any heat.
+    pub temperature: Celsius,
+}
+
+/// Strike water temperature for a single infusion mash
+///
+/// [Reference](http://howtobrew.com/book/section-3/the-methods-of-mashing/calculations-for-boiling-water-additions)
+///
+/// The heat released by the strike water when it cools to the target mash temperature
+/// heats the grain and the mash tun:
+/// $$
+///     T_w = T_t + \frac{c_g m_g (T_t - T_g) + C_{tun} (T_t - T_{tun})}{c_w r m_g},
+/// $$
+///
+/// - $T_w$ \[°C\]: Strike water temperature,
+/// - $T_t$ \[°C\]: Target mash temperature,
+/// - $T_g$ \[°C\]: Grain temperature,
+/// - $m_g$ \[kg\]: Grain mass,
+/// - $r$ \[l / kg\]: Mash thickness (water density assumed to be $1$ kg / l),
+/// - $c_g, c_w$ \[kJ / (kg K)\]: Specific heat of grain and water,
+///   see [`GRAIN_SPECIFIC_HEAT`] and [`WATER_SPECIFIC_HEAT`].
+///
+/// Without a mash tun, or with a preheated one, the grain mass cancels out and the formula
+/// reduces to the common
+/// $$
+///     T_w = T_t + \frac{c_g}{c_w r} (T_t - T_g).
+/// $$
+/// The grain weight and mash thickness must be positive, and inputs giving a water
+/// temperature below absolute zero give an error.
+///
+/// ```
+/// # use brew_calculator::calculations::{strike_water_temperature, GRAIN_SPECIFIC_HEAT};
+/// # use brew_calculator::units::temperature::{Celsius, Temperature};
+/// # use assert_approx_eq::assert_approx_eq;
+/// let target = Celsius::new(67.0).unwrap();
+/// let grain = Celsius::new(20.0).unwrap();
+/// let strike_temperature =
+///     strike_water_temperature(target, grain, 5.0, 3.0, GRAIN_SPECIFIC_HEAT, None).unwrap();
+/// assert_approx_eq!(strike_temperature.value, 73.44, 0.01);
+/// ```
+pub fn strike_water_temperature(
+    target_mash_temperature: Celsius,
+    grain_temperature: Celsius,
+    grain_weight: Kilograms,
+    mash_thickness: f32,
+    grain_specific_heat: f32,
+    mash_tun: Option<MashTun>,
+) -> Result<Celsius, dimension::Error> {
+    if grain_weight.is_nan() || grain_weight <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected positive grain weight, got: {}.",
+            grain_weight
+        )));
+    }
+    if mash_thickness.is_nan() || mash_thickness <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected positive mash thickness, got: {}.",
+            mash_thickness
+        )));
+    }
+    let water_heat_capacity =
+        WATER_SPECIFIC_HEAT * strike_water_volume(grain_weight, mash_thickness);
+    let mut excess = (target_mash_temperature - grain_temperature)
+        * (grain_specific_heat * grain_weight / water_heat_capacity);
+    if let Some(tun) = mash_tun {
+        excess = excess
+            + (target_mash_temperature - tun.temperature)
+                * (tun.thermal_mass / water_heat_capacity);
+    }
+    target_mash_temperature + excess
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::units::temperature::Temperature;
     use assert_approx_eq::assert_approx_eq;
     #[test]
     fn test_abv_from_gravity_diff() {
@@ -62,4 +154,65 @@ mod tests {
             assert_approx_eq!(value.2, abv, 0.001);
         }
     }
+
+    #[test]
+    fn test_strike_water_temperature() {
+        // Palmer's approximation: T_w = (0.41 / r) (T_t - T_g) + T_t
+        let strike_temperature = strike_water_temperature(
+            Celsius::new(66.0).unwrap(),
+            Celsius::new(18.0).unwrap(),
+            6.0,
+            2.5,
+            GRAIN_SPECIFIC_HEAT,
+            None,
+        )
+        .unwrap();
+        assert_approx_eq!(strike_temperature.value, 0.41 / 2.5 * 48.0 + 66.0, 0.1);
+    }
+
+    #[test]
+    fn test_strike_water_temperature_invalid_inputs() {
+        let (target, grain) = (Celsius::new(66.0).unwrap(), Celsius::new(18.0).unwrap());
+        let error = strike_water_temperature(target, grain, 0.0, 2.5, GRAIN_SPECIFIC_HEAT, None)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            dimension::Error::ValueError("Expected positive grain weight, got: 0.".into())
+        );
+        assert!(
+            strike_water_temperature(target, grain, 6.0, 0.0, GRAIN_SPECIFIC_HEAT, None).is_err()
+        );
+        assert!(
+            strike_water_temperature(target, grain, -6.0, 2.5, GRAIN_SPECIFIC_HEAT, None).is_err()
+        );
+    }
+
+    #[test]
+    fn test_strike_water_temperature_mash_tun() {
+        let (target, grain) = (Celsius::new(66.0).unwrap(), Celsius::new(18.0).unwrap());
+        let cold_tun = MashTun {
+            thermal_mass: 8.0,
+            temperature: grain,
+        };
+        let preheated_tun = MashTun {
+            thermal_mass: 8.0,
+            temperature: target,
+        };
+        let strike_temperature = |mash_tun| {
+            strike_water_temperature(target, grain, 6.0, 2.5, GRAIN_SPECIFIC_HEAT, mash_tun)
+                .unwrap()
+        };
+        let without_tun = strike_temperature(None);
+        // 8 kJ/K * 48 K / (4.184 kJ/(kg K) * 15 kg)
+        assert_approx_eq!(
+            (strike_temperature(Some(cold_tun)) - without_tun).value,
+            6.12,
+            0.01
+        );
+        assert_approx_eq!(
+            strike_temperature(Some(preheated_tun)).value,
+            without_tun.value,
+            0.001
+        );
+    }
 }
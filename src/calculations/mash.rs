@@ -0,0 +1,346 @@
+//! Mash schedules
+//!
+//! - $m_g$ \[kg\]: Grain mass,
+//! - $W$ \[l\]: Water in the mash (density assumed to be $1$ kg / l),
+//! - $r$ \[l / kg\]: Mash thickness, $r = W / m_g$,
+//! - $T_i$ \[°C\]: Mash temperature at rest $i$,
+//! - $T_w$ \[°C\]: Infusion water temperature,
+//! - $c_g, c_w$ \[kJ / (kg K)\]: Specific heat of grain and water,
+//! - $C_{tun}$ \[kJ / K\]: Heat capacity of the mash tun,
+//! - $T_d$ \[°C\]: Decoction temperature, usually boiling,
+//! - $V_m$ \[l\]: Total mash volume.
+
+use crate::calculations::{
+    strike_water_temperature, strike_water_volume, MashTun, WATER_SPECIFIC_HEAT,
+};
+use crate::units::dimension;
+use crate::units::temperature::Celsius;
+use crate::units::{Kilograms, Liters, Minutes};
+
+/// A rest in a mash schedule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MashStep {
+    /// Rest temperature $T_i$ \[°C\]
+    pub temperature: Celsius,
+    /// Rest duration \[min\]
+    pub duration: Minutes,
+}
+
+/// Step mash where each rest is reached by an infusion of hot water
+#[derive(Debug, Clone, PartialEq)]
+pub struct MashSchedule {
+    /// Grain mass $m_g$ \[kg\]
+    pub grain_weight: Kilograms,
+    /// Grain temperature before dough-in \[°C\]
+    pub grain_temperature: Celsius,
+    /// Mash thickness of the strike water $r_0$ \[l / kg\]
+    pub mash_thickness: f32,
+    /// Temperature $T_w$ \[°C\] of the infusions after the first rest, usually boiling water
+    pub infusion_temperature: Celsius,
+    /// Grain specific heat $c_g$ \[kJ / (kg K)\]
+    pub grain_specific_heat: f32,
+    /// Mash tun, see [`strike_water_temperature`]
+    pub mash_tun: Option<MashTun>,
+    /// Rests, in order
+    pub steps: Vec<MashStep>,
+}
+
+/// Water addition for a mash step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StepReport {
+    /// The step
+    pub step: MashStep,
+    /// Volume of the water added to reach the step \[l\]
+    pub infusion_volume: Liters,
+    /// Temperature of the water added to reach the step \[°C\]
+    pub infusion_temperature: Celsius,
+    /// Total water in the mash after the infusion \[l\]
+    pub total_water: Liters,
+    /// Mash thickness after the infusion \[l / kg\]
+    pub mash_thickness: f32,
+}
+
+/// Infusion volume to raise the mash temperature from $T_i$ to $T_{i+1}$.
+///
+/// [Reference](http://howtobrew.com/book/section-3/the-methods-of-mashing/calculations-for-boiling-water-additions)
+///
+/// $$
+///     V_{inf} = \frac{(T_{i+1} - T_i)(c_g m_g + c_w W + C_{tun})}{c_w (T_w - T_{i+1})}
+/// $$
+///
+/// The mash tun is assumed to be at the mash temperature $T_i$.
+pub fn infusion_volume(
+    current_temperature: Celsius,
+    target_temperature: Celsius,
+    infusion_temperature: Celsius,
+    grain_weight: Kilograms,
+    mash_water: Liters,
+    grain_specific_heat: f32,
+    mash_tun_thermal_mass: f32,
+) -> Result<Liters, dimension::Error> {
+    if infusion_temperature <= target_temperature {
+        return Err(dimension::Error::ValueError(format!(
+            "Infusion temperature {} must be above the target temperature {}.",
+            infusion_temperature.value, target_temperature.value
+        )));
+    }
+    if target_temperature < current_temperature {
+        return Err(dimension::Error::ValueError(format!(
+            "Infusions can not lower the mash temperature from {} to {}.",
+            current_temperature.value, target_temperature.value
+        )));
+    }
+    let mash_heat_capacity = grain_specific_heat * grain_weight
+        + WATER_SPECIFIC_HEAT * mash_water
+        + mash_tun_thermal_mass;
+    Ok(
+        (target_temperature - current_temperature) / (infusion_temperature - target_temperature)
+            * mash_heat_capacity
+            / WATER_SPECIFIC_HEAT,
+    )
+}
+
+/// Water additions for an infusion step mash
+///
+/// The first rest is reached with strike water at the temperature given by
+/// [`strike_water_temperature`], the following with infusions at the schedule's infusion
+/// temperature, see [`infusion_volume`].
+pub fn infusion_schedule(schedule: &MashSchedule) -> Result<Vec<StepReport>, dimension::Error> {
+    let mut steps = schedule.steps.iter();
+    let first_step = match steps.next() {
+        Some(step) => *step,
+        None => return Ok(Vec::new()),
+    };
+    let strike_volume = strike_water_volume(schedule.grain_weight, schedule.mash_thickness);
+    let mut reports = vec![StepReport {
+        step: first_step,
+        infusion_volume: strike_volume,
+        infusion_temperature: strike_water_temperature(
+            first_step.temperature,
+            schedule.grain_temperature,
+            schedule.grain_weight,
+            schedule.mash_thickness,
+            schedule.grain_specific_heat,
+            schedule.mash_tun,
+        )?,
+        total_water: strike_volume,
+        mash_thickness: schedule.mash_thickness,
+    }];
+    let tun_thermal_mass = schedule.mash_tun.map_or(0.0, |tun| tun.thermal_mass);
+    for step in steps {
+        let previous = reports[reports.len() - 1];
+        let volume = infusion_volume(
+            previous.step.temperature,
+            step.temperature,
+            schedule.infusion_temperature,
+            schedule.grain_weight,
+            previous.total_water,
+            schedule.grain_specific_heat,
+            tun_thermal_mass,
+        )?;
+        let total_water = previous.total_water + volume;
+        reports.push(StepReport {
+            step: *step,
+            infusion_volume: volume,
+            infusion_temperature: schedule.infusion_temperature,
+            total_water,
+            mash_thickness: total_water / schedule.grain_weight,
+        });
+    }
+    Ok(reports)
+}
+
+/// Decoction mash, where each rest is reached by boiling a part of the mash and returning it
+///
+/// One, two or three steps make a single, double or triple decoction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecoctionSchedule {
+    /// Total mash volume $V_m$ \[l\]
+    pub mash_volume: Liters,
+    /// Mash temperature before the first decoction \[°C\]
+    pub mash_temperature: Celsius,
+    /// Temperature $T_d$ \[°C\] of the decoction when it is returned to the mash
+    pub decoction_temperature: Celsius,
+    /// Rests reached by the decoctions, in order
+    pub steps: Vec<MashStep>,
+}
+
+/// Decoction pulled for a mash step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecoctionReport {
+    /// The step
+    pub step: MashStep,
+    /// Fraction of the mash to pull \[-\]
+    pub fraction: f32,
+    /// Volume of the mash to pull \[l\]
+    pub volume: Liters,
+}
+
+/// Fraction of the mash to pull to raise its temperature from $T_i$ to $T_{i+1}$
+///
+/// [Reference](http://howtobrew.com/book/section-3/the-methods-of-mashing/decoction-mashing)
+///
+/// The decoction and the rest of the mash are assumed to have the same heat capacity per
+/// volume, which holds for a thick decoction:
+/// $$
+///     f = \frac{T_{i+1} - T_i}{T_d - T_i}
+/// $$
+pub fn decoction_fraction(
+    current_temperature: Celsius,
+    target_temperature: Celsius,
+    decoction_temperature: Celsius,
+) -> Result<f32, dimension::Error> {
+    if decoction_temperature <= target_temperature {
+        return Err(dimension::Error::ValueError(format!(
+            "Decoction temperature {} must be above the target temperature {}.",
+            decoction_temperature.value, target_temperature.value
+        )));
+    }
+    if target_temperature < current_temperature {
+        return Err(dimension::Error::ValueError(format!(
+            "Decoctions can not lower the mash temperature from {} to {}.",
+            current_temperature.value, target_temperature.value
+        )));
+    }
+    Ok((target_temperature - current_temperature) / (decoction_temperature - current_temperature))
+}
+
+/// Decoctions for a decoction mash
+///
+/// The volume of each decoction is $f V_m$, see [`decoction_fraction`].
+pub fn decoction_schedule(
+    schedule: &DecoctionSchedule,
+) -> Result<Vec<DecoctionReport>, dimension::Error> {
+    let mut current_temperature = schedule.mash_temperature;
+    let mut reports = Vec::with_capacity(schedule.steps.len());
+    for step in schedule.steps.iter() {
+        let fraction = decoction_fraction(
+            current_temperature,
+            step.temperature,
+            schedule.decoction_temperature,
+        )?;
+        reports.push(DecoctionReport {
+            step: *step,
+            fraction,
+            volume: fraction * schedule.mash_volume,
+        });
+        current_temperature = step.temperature;
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::GRAIN_SPECIFIC_HEAT;
+    use crate::units::temperature::Temperature;
+    use assert_approx_eq::assert_approx_eq;
+
+    fn celsius(value: f32) -> Celsius {
+        Celsius::new(value).unwrap()
+    }
+
+    fn schedule() -> MashSchedule {
+        MashSchedule {
+            grain_weight: 5.0,
+            grain_temperature: celsius(20.0),
+            mash_thickness: 2.5,
+            infusion_temperature: celsius(100.0),
+            grain_specific_heat: GRAIN_SPECIFIC_HEAT,
+            mash_tun: None,
+            steps: vec![
+                MashStep {
+                    temperature: celsius(50.0),
+                    duration: 15.0,
+                },
+                MashStep {
+                    temperature: celsius(63.0),
+                    duration: 30.0,
+                },
+                MashStep {
+                    temperature: celsius(72.0),
+                    duration: 20.0,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_infusion_volume() {
+        // Palmer: W_a = (T2 - T1)(0.41 G + W_m) / (T_w - T2)
+        let volume = infusion_volume(
+            celsius(40.0),
+            celsius(60.0),
+            celsius(100.0),
+            4.0,
+            4.0,
+            GRAIN_SPECIFIC_HEAT,
+            0.0,
+        )
+        .unwrap();
+        assert_approx_eq!(volume, 20.0 * (0.411 * 4.0 + 4.0) / 40.0, 0.01);
+    }
+
+    #[test]
+    fn test_infusion_schedule() {
+        let reports = infusion_schedule(&schedule()).unwrap();
+        assert_eq!(reports.len(), 3);
+        assert_approx_eq!(reports[0].infusion_volume, 12.5, 0.001);
+        let mut total_water = 0.0;
+        for report in reports.iter() {
+            total_water += report.infusion_volume;
+            assert_approx_eq!(report.total_water, total_water, 0.001);
+            assert_approx_eq!(report.mash_thickness, total_water / 5.0, 0.001);
+        }
+        // Heat balance for the last infusion
+        let (previous, last) = (reports[1], reports[2]);
+        let released = last.infusion_volume * WATER_SPECIFIC_HEAT * (100.0 - 72.0);
+        let absorbed = (GRAIN_SPECIFIC_HEAT * 5.0 + WATER_SPECIFIC_HEAT * previous.total_water)
+            * (72.0 - 63.0);
+        assert_approx_eq!(released, absorbed, 0.01);
+    }
+
+    #[test]
+    fn test_infusion_schedule_decreasing_temperature() {
+        let mut schedule = schedule();
+        schedule.steps[2].temperature = celsius(60.0);
+        assert!(infusion_schedule(&schedule).is_err());
+    }
+
+    #[test]
+    fn test_decoction_fraction() {
+        let fraction = decoction_fraction(celsius(52.0), celsius(64.0), celsius(100.0)).unwrap();
+        assert_approx_eq!(fraction, 0.25, 0.0001);
+        assert!(decoction_fraction(celsius(64.0), celsius(52.0), celsius(100.0)).is_err());
+        assert!(decoction_fraction(celsius(52.0), celsius(100.0), celsius(100.0)).is_err());
+    }
+
+    #[test]
+    fn test_triple_decoction() {
+        let schedule = DecoctionSchedule {
+            mash_volume: 30.0,
+            mash_temperature: celsius(37.0),
+            decoction_temperature: celsius(100.0),
+            steps: vec![
+                MashStep {
+                    temperature: celsius(52.0),
+                    duration: 20.0,
+                },
+                MashStep {
+                    temperature: celsius(64.0),
+                    duration: 45.0,
+                },
+                MashStep {
+                    temperature: celsius(76.0),
+                    duration: 10.0,
+                },
+            ],
+        };
+        let reports = decoction_schedule(&schedule).unwrap();
+        let expected_fractions = [15.0 / 63.0, 12.0 / 48.0, 12.0 / 36.0];
+        for (report, expected) in reports.iter().zip(expected_fractions.iter()) {
+            assert_approx_eq!(report.fraction, expected, 0.0001);
+            assert_approx_eq!(report.volume, expected * 30.0, 0.001);
+        }
+    }
+}
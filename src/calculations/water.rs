@@ -0,0 +1,453 @@
+//! Brew day water volumes
+//!
+//! Working backwards from the batch size, each loss in the brewhouse adds to the water
+//! needed:
+//!
+//! - $V_b$ \[l\]: Batch size, the beer left after fermentation,
+//! - $V_f$ \[l\]: Fermenter loss (yeast cake and sediment),
+//! - $V_t$ \[l\]: Trub and chiller loss in the kettle,
+//! - $\dot{V}_{boil}$ \[l / h\]: Boil-off rate,
+//! - $t$ \[min\]: Boil time,
+//! - $a$ \[l / kg\]: Grain absorption,
+//! - $V_d$ \[l\]: Mash tun dead space,
+//! - $m_g$ \[kg\]: Grain mass,
+//! - $r$ \[l / kg\]: Mash thickness,
+//! - $d$ \[l / kg\]: Grain displacement, see [`GRAIN_DISPLACEMENT`].
+//!
+//! Thermal expansion of hot wort is not accounted for.
+
+use crate::calculations::{strike_water_temperature, strike_water_volume};
+use crate::units::dimension;
+use crate::units::mass::Kilogram;
+use crate::units::temperature::Celsius;
+use crate::units::volume::{Litre, Volume};
+use crate::units::Minutes;
+use serde::{Deserialize, Serialize};
+
+/// Volume displaced by the grain in the mash $d$ \[l / kg\]
+pub const GRAIN_DISPLACEMENT: f32 = 0.67;
+
+/// Brewhouse losses
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub struct Equipment {
+    /// Water retained by the spent grain $a$ \[l / kg\]
+    pub grain_absorption: f32,
+    /// Wort left under the false bottom $V_d$
+    pub mash_tun_dead_space: Litre,
+    /// Boil-off rate $\dot{V}_{boil}$ \[l / h\]
+    pub boil_off_rate: f32,
+    /// Wort left in the kettle and chiller $V_t$
+    pub trub_chiller_loss: Litre,
+    /// Beer left in the fermenter $V_f$
+    pub fermenter_loss: Litre,
+}
+
+/// Water volumes for a brew day
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub struct WaterPlan {
+    /// Strike water
+    pub strike_volume: Litre,
+    /// Sparge water
+    pub sparge_volume: Litre,
+    /// Wort collected in the kettle
+    pub pre_boil_volume: Litre,
+    /// Wort in the kettle after the boil
+    pub post_boil_volume: Litre,
+}
+
+impl WaterPlan {
+    /// Total water, strike and sparge
+    pub fn total_water(&self) -> Litre {
+        self.strike_volume + self.sparge_volume
+    }
+}
+
+/// Water retained by grain in a bag left to drip dry $a$ \[l / kg\]
+pub const DRAINED_BAG_ABSORPTION: f32 = 0.8;
+/// Water retained by grain in a squeezed bag $a$ \[l / kg\]
+pub const SQUEEZED_BAG_ABSORPTION: f32 = 0.5;
+
+/// How the bag is drained in brew-in-a-bag (BIAB)
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub enum BagDrainage {
+    /// The bag is left to drip dry, see [`DRAINED_BAG_ABSORPTION`]
+    Drained,
+    /// The bag is squeezed, see [`SQUEEZED_BAG_ABSORPTION`]
+    Squeezed,
+    /// The measured grain absorption of the equipment, [`Equipment::grain_absorption`]
+    Measured,
+}
+
+impl BagDrainage {
+    /// Water retained by the grain in the bag $a$ \[l / kg\]
+    pub fn grain_absorption(self, equipment: &Equipment) -> f32 {
+        match self {
+            BagDrainage::Drained => DRAINED_BAG_ABSORPTION,
+            BagDrainage::Squeezed => SQUEEZED_BAG_ABSORPTION,
+            BagDrainage::Measured => equipment.grain_absorption,
+        }
+    }
+}
+
+/// Wort volumes after and before the boil
+///
+/// $$
+///     V_{post} = V_b + V_f + V_t
+/// $$
+/// $$
+///     V_{pre} = V_{post} + \dot{V}_{boil} \frac{t}{60}
+/// $$
+fn boil_volumes(
+    batch_size: Litre,
+    boil_time: Minutes,
+    equipment: &Equipment,
+) -> Result<(Litre, Litre), dimension::Error> {
+    let post_boil_volume = batch_size + equipment.fermenter_loss + equipment.trub_chiller_loss;
+    let pre_boil_volume =
+        post_boil_volume + Litre::new(equipment.boil_off_rate * boil_time / 60.0)?;
+    Ok((pre_boil_volume, post_boil_volume))
+}
+
+/// Batch size and grain weight must be positive for a water plan
+fn validate_batch(batch_size: Litre, grain_weight: Kilogram) -> Result<(), dimension::Error> {
+    if batch_size.value <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected positive batch size, got: {}.",
+            batch_size
+        )));
+    }
+    if grain_weight.value <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected positive grain weight, got: {}.",
+            grain_weight
+        )));
+    }
+    Ok(())
+}
+
+/// Water volumes for a mash with sparge
+///
+/// The strike water is given by [`strike_water_volume`] and the sparge water makes up the
+/// rest of the total water,
+/// $$
+///     V_{total} = V_{pre} + a m_g + V_d,
+/// $$
+/// $$
+///     V_{sparge} = V_{total} - r m_g.
+/// $$
+///
+/// ```
+/// # use brew_calculator::calculations::water::{water_plan, Equipment};
+/// # use brew_calculator::units::mass::{Kilogram, Mass};
+/// # use brew_calculator::units::volume::{Litre, Volume};
+/// # use assert_approx_eq::assert_approx_eq;
+/// let equipment = Equipment {
+///     grain_absorption: 1.0,
+///     mash_tun_dead_space: Litre::new(2.0).unwrap(),
+///     boil_off_rate: 4.0,
+///     trub_chiller_loss: Litre::new(1.5).unwrap(),
+///     fermenter_loss: Litre::new(1.0).unwrap(),
+/// };
+/// let plan = water_plan(
+///     Litre::new(20.0).unwrap(),
+///     Kilogram::new(5.0).unwrap(),
+///     3.0,
+///     60.0,
+///     &equipment,
+/// )
+/// .unwrap();
+/// assert_approx_eq!(plan.pre_boil_volume.value, 26.5, 0.001);
+/// assert_approx_eq!(plan.sparge_volume.value, 18.5, 0.001);
+/// ```
+pub fn water_plan(
+    batch_size: Litre,
+    grain_weight: Kilogram,
+    mash_thickness: f32,
+    boil_time: Minutes,
+    equipment: &Equipment,
+) -> Result<WaterPlan, dimension::Error> {
+    validate_batch(batch_size, grain_weight)?;
+    let (pre_boil_volume, post_boil_volume) = boil_volumes(batch_size, boil_time, equipment)?;
+    let total_water = pre_boil_volume
+        + Litre::new(equipment.grain_absorption * grain_weight.value)?
+        + equipment.mash_tun_dead_space;
+    let strike_volume = Litre::new(strike_water_volume(grain_weight.value, mash_thickness))?;
+    if strike_volume > total_water {
+        return Err(dimension::Error::ValueError(format!(
+            "Strike water {} exceeds the total water {}, use a thicker mash.",
+            strike_volume, total_water
+        )));
+    }
+    Ok(WaterPlan {
+        strike_volume,
+        sparge_volume: (total_water - strike_volume)?,
+        pre_boil_volume,
+        post_boil_volume,
+    })
+}
+
+/// Water volumes for a brew-in-a-bag (BIAB) full volume mash
+///
+/// All water goes in as strike water and there is no sparge.
+/// The grain absorption depends on how the bag is drained, see [`BagDrainage`], so
+/// [`Equipment::grain_absorption`] is only used for [`BagDrainage::Measured`].
+/// [`Equipment::mash_tun_dead_space`] is not used, as the wort under the bag is already in
+/// the kettle:
+/// $$
+///     V_{strike} = V_{pre} + a m_g
+/// $$
+///
+/// The kettle has to hold both the water and the grain, which fails if
+/// $$
+///     V_{strike} + d m_g > V_{kettle}.
+/// $$
+pub fn biab_water_plan(
+    batch_size: Litre,
+    grain_weight: Kilogram,
+    boil_time: Minutes,
+    drainage: BagDrainage,
+    kettle_volume: Litre,
+    equipment: &Equipment,
+) -> Result<WaterPlan, dimension::Error> {
+    validate_batch(batch_size, grain_weight)?;
+    let (pre_boil_volume, post_boil_volume) = boil_volumes(batch_size, boil_time, equipment)?;
+    let strike_volume =
+        pre_boil_volume + Litre::new(drainage.grain_absorption(equipment) * grain_weight.value)?;
+    let kettle = check_capacity(mash_volume(grain_weight, strike_volume), kettle_volume);
+    if kettle.exceeds_capacity {
+        return Err(dimension::Error::ValueError(format!(
+            "Mash volume {} exceeds the kettle volume {}.",
+            kettle.mash_volume, kettle_volume
+        )));
+    }
+    Ok(WaterPlan {
+        strike_volume,
+        sparge_volume: Litre::new(0.0)?,
+        pre_boil_volume,
+        post_boil_volume,
+    })
+}
+
+/// Total volume of a mash
+///
+/// $$
+///     V_m = V_{strike} + d m_g
+/// $$
+pub fn mash_volume(grain_weight: Kilogram, strike_volume: Litre) -> Litre {
+    let grain_volume =
+        Litre::new(GRAIN_DISPLACEMENT * grain_weight.value).expect("Non-negative grain volume");
+    strike_volume + grain_volume
+}
+
+/// Mash volume compared with the vessel it is mashed in
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub struct CapacityCheck {
+    /// Mash volume $V_m$
+    pub mash_volume: Litre,
+    /// Whether the mash volume exceeds the vessel volume
+    pub exceeds_capacity: bool,
+}
+
+/// Check that a mash fits in the mash tun
+///
+/// Gives the mash volume $V_m$, see [`mash_volume`], with the strike water from
+/// [`strike_water_volume`], and warns if it exceeds the mash tun volume.
+/// It is a warning rather than an error, so that a recipe can still be scaled and then
+/// split over several mashes or adjusted to a thicker mash.
+///
+/// ```
+/// # use brew_calculator::calculations::water::check_mash_tun_capacity;
+/// # use brew_calculator::units::mass::{Kilogram, Mass};
+/// # use brew_calculator::units::volume::{Litre, Volume};
+/// # use assert_approx_eq::assert_approx_eq;
+/// let mash_tun = Litre::new(30.0).unwrap();
+/// let check = check_mash_tun_capacity(Kilogram::new(6.0).unwrap(), 3.0, mash_tun).unwrap();
+/// assert_approx_eq!(check.mash_volume.value, 22.02, 0.001);
+/// assert!(!check.exceeds_capacity);
+/// let check = check_mash_tun_capacity(Kilogram::new(12.0).unwrap(), 3.0, mash_tun).unwrap();
+/// assert!(check.exceeds_capacity);
+/// ```
+pub fn check_mash_tun_capacity(
+    grain_weight: Kilogram,
+    mash_thickness: f32,
+    mash_tun_volume: Litre,
+) -> Result<CapacityCheck, dimension::Error> {
+    let strike_volume = Litre::new(strike_water_volume(grain_weight.value, mash_thickness))?;
+    Ok(check_capacity(
+        mash_volume(grain_weight, strike_volume),
+        mash_tun_volume,
+    ))
+}
+
+fn check_capacity(mash_volume: Litre, vessel_volume: Litre) -> CapacityCheck {
+    CapacityCheck {
+        mash_volume,
+        exceeds_capacity: mash_volume > vessel_volume,
+    }
+}
+
+/// Strike water temperature for a brew-in-a-bag (BIAB) full volume mash
+///
+/// The mash thickness is that of the full volume, $r = V_{strike} / m_g$,
+/// see [`strike_water_temperature`].
+/// The water is heated in the kettle, which is therefore not accounted for as a mash tun.
+pub fn biab_strike_water_temperature(
+    target_mash_temperature: Celsius,
+    grain_temperature: Celsius,
+    grain_weight: Kilogram,
+    plan: &WaterPlan,
+    grain_specific_heat: f32,
+) -> Result<Celsius, dimension::Error> {
+    strike_water_temperature(
+        target_mash_temperature,
+        grain_temperature,
+        grain_weight.value,
+        plan.strike_volume.value / grain_weight.value,
+        grain_specific_heat,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::GRAIN_SPECIFIC_HEAT;
+    use crate::units::mass::Mass;
+    use crate::units::temperature::Temperature;
+    use assert_approx_eq::assert_approx_eq;
+
+    fn equipment() -> Equipment {
+        Equipment {
+            grain_absorption: 0.8,
+            mash_tun_dead_space: Litre::new(1.0).unwrap(),
+            boil_off_rate: 3.0,
+            trub_chiller_loss: Litre::new(1.0).unwrap(),
+            fermenter_loss: Litre::new(2.0).unwrap(),
+        }
+    }
+
+    fn litres(value: f32) -> Litre {
+        Litre::new(value).unwrap()
+    }
+
+    fn kilograms(value: f32) -> Kilogram {
+        Kilogram::new(value).unwrap()
+    }
+
+    #[test]
+    fn test_water_plan() {
+        let plan = water_plan(litres(23.0), kilograms(5.5), 2.7, 90.0, &equipment()).unwrap();
+        assert_approx_eq!(plan.post_boil_volume.value, 26.0, 0.001);
+        assert_approx_eq!(plan.pre_boil_volume.value, 30.5, 0.001);
+        assert_approx_eq!(plan.strike_volume.value, 14.85, 0.001);
+        assert_approx_eq!(plan.total_water().value, 30.5 + 4.4 + 1.0, 0.001);
+        assert_approx_eq!(plan.sparge_volume.value, 35.9 - 14.85, 0.001);
+    }
+
+    #[test]
+    fn test_water_plan_too_thin_mash() {
+        assert!(water_plan(litres(5.0), kilograms(5.5), 5.0, 60.0, &equipment()).is_err());
+    }
+
+    #[test]
+    fn test_water_plan_invalid_inputs() {
+        assert!(water_plan(litres(0.0), kilograms(5.5), 2.7, 60.0, &equipment()).is_err());
+        assert!(water_plan(litres(23.0), kilograms(0.0), 2.7, 60.0, &equipment()).is_err());
+        assert!(water_plan(litres(23.0), kilograms(5.5), -2.7, 60.0, &equipment()).is_err());
+        let kettle = litres(50.0);
+        let biab = |batch_size, grain_weight| {
+            biab_water_plan(
+                batch_size,
+                grain_weight,
+                60.0,
+                BagDrainage::Squeezed,
+                kettle,
+                &equipment(),
+            )
+        };
+        assert!(biab(litres(0.0), kilograms(5.0)).is_err());
+        assert!(biab(litres(23.0), kilograms(0.0)).is_err());
+    }
+
+    #[test]
+    fn test_biab_water_plan() {
+        let (batch_size, grain_weight, kettle) = (litres(23.0), kilograms(5.0), litres(50.0));
+        let biab = |drainage| {
+            biab_water_plan(
+                batch_size,
+                grain_weight,
+                60.0,
+                drainage,
+                kettle,
+                &equipment(),
+            )
+            .unwrap()
+        };
+        let plan = biab(BagDrainage::Squeezed);
+        assert_approx_eq!(plan.pre_boil_volume.value, 29.0, 0.001);
+        assert_approx_eq!(plan.strike_volume.value, 31.5, 0.001);
+        assert_approx_eq!(plan.sparge_volume.value, 0.0, 0.001);
+
+        let drained = biab(BagDrainage::Drained);
+        assert!(drained.strike_volume > plan.strike_volume);
+
+        let measured = biab(BagDrainage::Measured);
+        assert_approx_eq!(
+            measured.strike_volume.value,
+            drained.strike_volume.value,
+            0.001
+        );
+    }
+
+    #[test]
+    fn test_biab_kettle_capacity() {
+        // 31.5 l water and 5 kg * 0.67 l/kg grain does not fit in 34 l.
+        let plan = biab_water_plan(
+            litres(23.0),
+            kilograms(5.0),
+            60.0,
+            BagDrainage::Squeezed,
+            litres(34.0),
+            &equipment(),
+        );
+        assert!(plan.is_err());
+    }
+
+    #[test]
+    fn test_biab_strike_water_temperature() {
+        let grain_weight = kilograms(5.0);
+        let plan = biab_water_plan(
+            litres(23.0),
+            grain_weight,
+            60.0,
+            BagDrainage::Squeezed,
+            litres(50.0),
+            &equipment(),
+        )
+        .unwrap();
+        let temperature = biab_strike_water_temperature(
+            Celsius::new(66.0).unwrap(),
+            Celsius::new(20.0).unwrap(),
+            grain_weight,
+            &plan,
+            GRAIN_SPECIFIC_HEAT,
+        )
+        .unwrap();
+        assert_approx_eq!(temperature.value, 66.0 + 1.72 / 4.184 / 6.3 * 46.0, 0.01);
+    }
+
+    #[test]
+    fn test_mash_volume() {
+        assert_approx_eq!(
+            mash_volume(kilograms(5.0), litres(15.0)).value,
+            18.35,
+            0.001
+        );
+        let check = check_mash_tun_capacity(kilograms(5.0), 3.0, litres(20.0)).unwrap();
+        assert_approx_eq!(check.mash_volume.value, 18.35, 0.001);
+        assert!(!check.exceeds_capacity);
+        let check = check_mash_tun_capacity(kilograms(5.0), 3.0, litres(17.0)).unwrap();
+        assert!(check.exceeds_capacity);
+        assert!(check_mash_tun_capacity(kilograms(5.0), -3.0, litres(20.0)).is_err());
+    }
+}
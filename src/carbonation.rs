@@ -0,0 +1,697 @@
+//! Carbonation
+//!
+//! - $V_{CO_2}$ \[-\]: Dissolved CO2 in volumes, litres of gas at standard conditions
+//!   per litre of beer,
+//! - $T$ \[°F\]: Beer temperature, for priming the highest after fermentation,
+//! - $V$ \[l\]: Beer volume,
+//! - $P$ \[psi\]: Regulator gauge pressure,
+//! - $m$ \[g\]: Priming sugar mass,
+//! - $e$ \[-\]: Sucrose equivalent of the priming sugar, see [`PrimingSugar`].
+
+use crate::units::concentration::{Concentration, Plato, SpecificGravity, VolumesCO2};
+use crate::units::dimension;
+use crate::units::format::UnitSystem;
+use crate::units::mass::{Gram, Mass};
+use crate::units::pressure::{Bar, Pressure, Psi};
+use crate::units::temperature::{Celsius, Fahrenheit, TemperatureDifference};
+use crate::units::volume::Litre;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+/// Mass of one volume of CO2 per litre of beer \[g / l\]
+const CO2_PER_VOLUME: f32 = 1.977;
+/// Peak temperatures \[°C\] the residual CO2 fit is valid for
+const RESIDUAL_CO2_TEMPERATURES: RangeInclusive<f32> = 0.0..=30.0;
+/// Mass of CO2 produced by fermenting sucrose \[g / g\]
+const CO2_PER_SUCROSE: f32 = 0.514;
+/// Mass of CO2 produced by fermenting wort extract \[g / g\], by Balling $2.0665$ g of
+/// extract gives $0.9565$ g of CO2
+const CO2_PER_EXTRACT: f32 = 0.9565 / 2.0665;
+/// Change in real extract per change in apparent extract, from Balling's
+/// $RE = 0.1808 \cdot OE + 0.8192 \cdot AE$
+const REAL_PER_APPARENT_EXTRACT: f32 = 0.8192;
+
+/// Sugar for bottle conditioning
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub enum PrimingSugar {
+    /// Corn sugar, glucose monohydrate
+    Dextrose,
+    /// Table sugar
+    Sucrose,
+    /// Dry malt extract
+    DryMaltExtract,
+    /// Honey
+    Honey,
+    /// Maple syrup
+    MapleSyrup,
+    /// Any other sugar
+    Other {
+        /// Fermentable sugar relative to sucrose $e$ \[-\]
+        sucrose_equivalent: f32,
+    },
+}
+
+impl PrimingSugar {
+    /// Fermentable sugar relative to sucrose $e$ \[-\]
+    ///
+    /// Accounts for water content and unfermentable sugars.
+    /// Honey, maple syrup and DME vary between products, the values are typical ones.
+    pub fn sucrose_equivalent(self) -> f32 {
+        match self {
+            PrimingSugar::Dextrose => 0.91,
+            PrimingSugar::Sucrose => 1.0,
+            PrimingSugar::DryMaltExtract => 0.68,
+            PrimingSugar::Honey => 0.74,
+            PrimingSugar::MapleSyrup => 0.66,
+            PrimingSugar::Other { sucrose_equivalent } => sucrose_equivalent,
+        }
+    }
+}
+
+/// Priming sugar for bottle conditioning
+///
+/// The beer keeps the CO2 dissolved at the highest temperature it reached after
+/// fermentation, and the sugar makes up the rest:
+/// $$
+///     m = \frac{1.977 (V_{CO_2} - V_{CO_2, residual}) V}{0.514 \cdot e},
+/// $$
+/// where $1.977$ g / l is the mass of one volume of CO2 and sucrose ferments to $0.514$ g
+/// CO2 per g.
+///
+/// ```
+/// # use brew_calculator::carbonation::{priming_sugar, PrimingSugar};
+/// # use brew_calculator::units::concentration::{Concentration, VolumesCO2};
+/// # use brew_calculator::units::temperature::{Celsius, Temperature};
+/// # use brew_calculator::units::volume::{Litre, Volume};
+/// # use assert_approx_eq::assert_approx_eq;
+/// let sugar = priming_sugar(
+///     VolumesCO2::new(2.5).unwrap(),
+///     Litre::new(20.0).unwrap(),
+///     Celsius::new(20.0).unwrap(),
+///     PrimingSugar::Sucrose,
+/// )
+/// .unwrap();
+/// assert_approx_eq!(sugar.value, 126.0, 0.1);
+/// ```
+pub fn priming_sugar(
+    target: VolumesCO2,
+    beer_volume: Litre,
+    peak_temperature: Celsius,
+    sugar: PrimingSugar,
+) -> Result<Gram, dimension::Error> {
+    let residual = residual_co2(peak_temperature)?;
+    if target < residual {
+        return Err(dimension::Error::ValueError(format!(
+            "Target carbonation {} is below the residual {}.",
+            target, residual
+        )));
+    }
+    let sucrose_equivalent = sugar.sucrose_equivalent();
+    if sucrose_equivalent.is_nan() || sucrose_equivalent <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected positive sucrose equivalent, got: {}.",
+            sucrose_equivalent
+        )));
+    }
+    let sucrose = CO2_PER_VOLUME * (target - residual).value * beer_volume.value / CO2_PER_SUCROSE;
+    Gram::new(sucrose / sucrose_equivalent)
+}
+
+/// CO2 dissolved in flat beer after fermentation
+///
+/// Fermentation saturates the beer with CO2 at atmospheric pressure, and the beer keeps
+/// what is soluble at the highest temperature it reached afterwards:
+/// $$
+///     V_{CO_2, residual} = 3.0378 - 0.050062 T + 0.00026555 T^2,
+/// $$
+/// a fit to CO2 solubility data.
+/// The parabola turns upwards above about 34 °C, so peak temperatures outside the
+/// $0$–$30$ °C the fit is valid for give an error.
+///
+/// ```
+/// # use brew_calculator::carbonation::residual_co2;
+/// # use brew_calculator::units::temperature::{Celsius, Temperature};
+/// # use assert_approx_eq::assert_approx_eq;
+/// let residual = residual_co2(Celsius::new(20.0).unwrap()).unwrap();
+/// assert_approx_eq!(residual.value, 0.86, 0.01);
+/// ```
+pub fn residual_co2(peak_temperature: Celsius) -> Result<VolumesCO2, dimension::Error> {
+    if !RESIDUAL_CO2_TEMPERATURES.contains(&peak_temperature.value) {
+        return Err(dimension::Error::ValueError(format!(
+            "Residual CO2 is only known for peak temperatures of {} to {} °C, got: {}.",
+            RESIDUAL_CO2_TEMPERATURES.start(),
+            RESIDUAL_CO2_TEMPERATURES.end(),
+            peak_temperature
+        )));
+    }
+    let temperature = Fahrenheit::from(peak_temperature).value;
+    let residual = 3.0378 - 0.050_062 * temperature + 0.000_265_55 * temperature.powi(2);
+    VolumesCO2::new(residual)
+}
+
+/// Coefficients of the force carbonation fit, the constant, $T$, $T^2$, $TV$, $V$ and $V^2$
+/// terms
+const FORCE_CARBONATION: [f32; 6] = [
+    -16.6999,
+    -0.010_105_9,
+    0.001_165_12,
+    0.173_354,
+    4.242_67,
+    -0.068_422_6,
+];
+
+/// Regulator pressure for force carbonation
+///
+/// The beer reaches the carbonation level in equilibrium with the pressure at the serving
+/// temperature:
+/// $$
+///     P = -16.6999 - 0.0101059 T + 0.00116512 T^2 + 0.173354 T V_{CO_2} + 4.24267 V_{CO_2}
+///         - 0.0684226 V_{CO_2}^2.
+/// $$
+/// Levels below the equilibrium at atmospheric pressure give an error.
+///
+/// ```
+/// # use brew_calculator::carbonation::force_carbonation_pressure;
+/// # use brew_calculator::units::concentration::{Concentration, VolumesCO2};
+/// # use brew_calculator::units::pressure::Psi;
+/// # use brew_calculator::units::temperature::{Fahrenheit, Temperature};
+/// # use assert_approx_eq::assert_approx_eq;
+/// let pressure = force_carbonation_pressure(
+///     VolumesCO2::new(2.5).unwrap(),
+///     Fahrenheit::new(38.0).unwrap().into(),
+/// )
+/// .unwrap();
+/// assert_approx_eq!(Psi::from(pressure).value, 11.25, 0.01);
+/// ```
+pub fn force_carbonation_pressure(
+    target: VolumesCO2,
+    temperature: Celsius,
+) -> Result<Bar, dimension::Error> {
+    let psi = equilibrium_pressure(target, temperature);
+    if psi < 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Carbonation {} is reached without pressure at {}.",
+            target, temperature
+        )));
+    }
+    Ok(Psi::new(psi)?.into())
+}
+
+/// Gauge pressure \[psi\] in equilibrium with the carbonation level, negative when the
+/// level is below the equilibrium at atmospheric pressure
+fn equilibrium_pressure(carbonation: VolumesCO2, temperature: Celsius) -> f32 {
+    let [constant, linear_t, square_t, cross, linear_v, square_v] = FORCE_CARBONATION;
+    let t = Fahrenheit::from(temperature).value;
+    let v = carbonation.value;
+    constant
+        + linear_t * t
+        + square_t * t.powi(2)
+        + cross * t * v
+        + linear_v * v
+        + square_v * v.powi(2)
+}
+
+/// Carbonation level from the regulator pressure and serving temperature
+///
+/// The inverse of [`force_carbonation_pressure`], the lower root of the quadratic in
+/// $V_{CO_2}$.
+pub fn force_carbonation_volumes(
+    pressure: Bar,
+    temperature: Celsius,
+) -> Result<VolumesCO2, dimension::Error> {
+    let [constant, linear_t, square_t, cross, linear_v, square_v] = FORCE_CARBONATION;
+    let t = Fahrenheit::from(temperature).value;
+    let a = square_v;
+    let b = linear_v + cross * t;
+    let c = constant + linear_t * t + square_t * t.powi(2) - Psi::from(pressure).value;
+    let discriminant = b.powi(2) - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "No carbonation level for {} at {}.",
+            pressure, temperature
+        )));
+    }
+    // Numerically stable form of (-b + sqrt(D)) / 2a
+    VolumesCO2::new(2.0 * c / (-b - discriminant.sqrt()))
+}
+
+/// Largest number of rows or columns in a [`CarbonationTable`]
+pub const MAX_TABLE_STEPS: usize = 1000;
+
+/// Force carbonation pressures over serving temperatures and carbonation levels
+#[derive(Debug, Clone, PartialEq)]
+pub struct CarbonationTable {
+    /// Serving temperatures, one row each
+    pub temperatures: Vec<Celsius>,
+    /// Carbonation levels, one column each
+    pub volumes: Vec<VolumesCO2>,
+    /// Regulator pressure per temperature and level,
+    /// `None` where the level is reached without pressure
+    pub pressures: Vec<Vec<Option<Bar>>>,
+}
+
+impl CarbonationTable {
+    /// Fixed width text table in the given unit system
+    pub fn format(&self, system: UnitSystem) -> String {
+        const WIDTH: usize = 10;
+        let mut text = format!("{:>WIDTH$}", "");
+        for volumes in &self.volumes {
+            let _ = write!(text, "{:>WIDTH$}", volumes.to_string());
+        }
+        text.push('\n');
+        for (temperature, row) in self.temperatures.iter().zip(&self.pressures) {
+            let _ = write!(text, "{:>WIDTH$}", system.temperature(*temperature));
+            for pressure in row {
+                let cell = match pressure {
+                    Some(pressure) => system.pressure(*pressure),
+                    None => "-".into(),
+                };
+                let _ = write!(text, "{:>WIDTH$}", cell);
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Force carbonation pressures over ranges of temperatures and levels
+///
+/// Both ranges run from the first value in steps up to and including the last value.
+/// Empty or infinite ranges, non-positive steps and ranges of more than
+/// [`MAX_TABLE_STEPS`] values give an error.
+///
+/// ```
+/// # use brew_calculator::carbonation::carbonation_table;
+/// # use brew_calculator::units::concentration::{Concentration, VolumesCO2};
+/// # use brew_calculator::units::format::UnitSystem;
+/// # use brew_calculator::units::pressure::Psi;
+/// # use brew_calculator::units::temperature::{Celsius, Temperature, TemperatureDifference};
+/// # use assert_approx_eq::assert_approx_eq;
+/// let table = carbonation_table(
+///     Celsius::new(2.0).unwrap(),
+///     Celsius::new(6.0).unwrap(),
+///     TemperatureDifference::new(2.0).unwrap(),
+///     VolumesCO2::new(2.2).unwrap(),
+///     VolumesCO2::new(2.6).unwrap(),
+///     VolumesCO2::new(0.2).unwrap(),
+/// )
+/// .unwrap();
+/// assert_eq!(table.temperatures.len(), 3);
+/// assert_eq!(table.volumes.len(), 3);
+/// // 2.6 volumes at 4 °C
+/// let pressure = table.pressures[1][2].unwrap();
+/// assert_approx_eq!(Psi::from(pressure).value, 12.93, 0.01);
+/// assert!(table.format(UnitSystem::Metric).contains("0.89 bar"));
+/// ```
+pub fn carbonation_table(
+    first_temperature: Celsius,
+    last_temperature: Celsius,
+    temperature_step: TemperatureDifference,
+    first_volumes: VolumesCO2,
+    last_volumes: VolumesCO2,
+    volumes_step: VolumesCO2,
+) -> Result<CarbonationTable, dimension::Error> {
+    let temperatures = (0..steps(
+        first_temperature.value,
+        last_temperature.value,
+        temperature_step.value,
+    )?)
+        .map(|step| first_temperature + temperature_step * step as f32)
+        .collect::<Result<Vec<_>, _>>()?;
+    let volumes = (0..steps(first_volumes.value, last_volumes.value, volumes_step.value)?)
+        .map(|step| VolumesCO2::new(first_volumes.value + volumes_step.value * step as f32))
+        .collect::<Result<Vec<_>, _>>()?;
+    let pressures = temperatures
+        .iter()
+        .map(|temperature| {
+            volumes
+                .iter()
+                .map(|target| force_carbonation_pressure(*target, *temperature).ok())
+                .collect()
+        })
+        .collect();
+    Ok(CarbonationTable {
+        temperatures,
+        volumes,
+        pressures,
+    })
+}
+
+/// Number of values in the range from first to last
+fn steps(first: f32, last: f32, step: f32) -> Result<usize, dimension::Error> {
+    if !(first.is_finite() && last.is_finite()) {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected a finite range, got: {} to {}.",
+            first, last
+        )));
+    }
+    if step.is_nan() || step <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected positive step, got: {}.",
+            step
+        )));
+    }
+    if last.is_nan() || last < first {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected the last value to be at least {}, got: {}.",
+            first, last
+        )));
+    }
+    // Allow for rounding in the step so the last value is included
+    let steps = ((last - first) / step + 1e-3).floor() + 1.0;
+    if steps > MAX_TABLE_STEPS as f32 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected at most {} values from {} to {}, got a step of {}.",
+            MAX_TABLE_STEPS, first, last, step
+        )));
+    }
+    Ok(steps as usize)
+}
+
+/// Bottle strength, as the highest carbonation level it safely holds
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum BottleRating {
+    /// Standard beer bottle, up to 3 volumes
+    #[default]
+    Standard,
+    /// Heavy Belgian style bottle, up to 4 volumes
+    Belgian,
+    /// Champagne bottle, up to 5 volumes
+    Champagne,
+}
+
+impl BottleRating {
+    /// Highest carbonation level, conservative as bottles vary and weaken with reuse
+    pub fn max_carbonation(self) -> VolumesCO2 {
+        let volumes = match self {
+            BottleRating::Standard => 3.0,
+            BottleRating::Belgian => 4.0,
+            BottleRating::Champagne => 5.0,
+        };
+        VolumesCO2::new(volumes).expect("Positive carbonation")
+    }
+}
+
+/// Extract left to ferment when bottling before reaching the final gravity
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub struct ResidualExtract {
+    /// Gravity at bottling
+    pub current_gravity: SpecificGravity,
+    /// Expected final gravity
+    pub final_gravity: SpecificGravity,
+}
+
+impl ResidualExtract {
+    /// CO2 from fermenting the rest of the extract \[g / l\]
+    ///
+    /// The apparent extract drop is converted to the drop in real extract,
+    /// of which $0.9565 / 2.0665$ ferments to CO2.
+    fn co2(&self) -> f32 {
+        let current = Plato::from(self.current_gravity).value;
+        let remaining = current - Plato::from(self.final_gravity).value;
+        // Plato is g extract per 100 g of beer
+        let extract =
+            REAL_PER_APPARENT_EXTRACT * remaining.max(0.0) * 10.0 * self.current_gravity.value;
+        CO2_PER_EXTRACT * extract
+    }
+}
+
+/// Bottle conditioning of a batch
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub struct BottleConditioning {
+    /// Priming sugar added to the batch $m$
+    pub priming_sugar: Gram,
+    /// Kind of priming sugar
+    pub sugar: PrimingSugar,
+    /// Beer volume $V$
+    pub beer_volume: Litre,
+    /// Highest temperature of the beer after fermentation
+    pub peak_temperature: Celsius,
+    /// Unfermented extract, if bottled before reaching the final gravity
+    pub residual_extract: Option<ResidualExtract>,
+}
+
+/// Expected carbonation and pressure in the bottles
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub struct BottlePressure {
+    /// Carbonation when all sugar and extract has fermented
+    pub carbonation: VolumesCO2,
+    /// Gauge pressure at the storage temperature
+    pub pressure: Bar,
+    /// Whether the carbonation is above what the bottles are rated for
+    pub exceeds_rating: bool,
+}
+
+/// Bottle pressure check for bottle conditioning
+///
+/// The carbonation is the residual CO2 plus the CO2 from the priming sugar and any
+/// unfermented extract, see [`priming_sugar`] and [`ResidualExtract`].
+/// The pressure is the equilibrium pressure of the carbonation at the storage temperature,
+/// see [`force_carbonation_pressure`].
+/// A beer volume that is not positive gives an error.
+///
+/// ```
+/// # use brew_calculator::carbonation::*;
+/// # use brew_calculator::units::concentration::{Concentration, SpecificGravity};
+/// # use brew_calculator::units::mass::{Gram, Mass};
+/// # use brew_calculator::units::temperature::{Celsius, Temperature};
+/// # use brew_calculator::units::volume::{Litre, Volume};
+/// let conditioning = BottleConditioning {
+///     priming_sugar: Gram::new(126.0).unwrap(),
+///     sugar: PrimingSugar::Sucrose,
+///     beer_volume: Litre::new(20.0).unwrap(),
+///     peak_temperature: Celsius::new(20.0).unwrap(),
+///     // Bottled five points before the final gravity
+///     residual_extract: Some(ResidualExtract {
+///         current_gravity: SpecificGravity::new(1.015).unwrap(),
+///         final_gravity: SpecificGravity::new(1.010).unwrap(),
+///     }),
+/// };
+/// let check = bottle_pressure(
+///     &conditioning,
+///     Celsius::new(20.0).unwrap(),
+///     BottleRating::Standard,
+/// )
+/// .unwrap();
+/// assert!(check.exceeds_rating);
+/// ```
+pub fn bottle_pressure(
+    conditioning: &BottleConditioning,
+    storage_temperature: Celsius,
+    rating: BottleRating,
+) -> Result<BottlePressure, dimension::Error> {
+    if conditioning.beer_volume.value <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected positive beer volume, got: {}.",
+            conditioning.beer_volume
+        )));
+    }
+    let sucrose = conditioning.priming_sugar.value * conditioning.sugar.sucrose_equivalent();
+    let mut co2 = CO2_PER_SUCROSE * sucrose / conditioning.beer_volume.value;
+    if let Some(residual_extract) = conditioning.residual_extract {
+        co2 += residual_extract.co2();
+    }
+    let carbonation =
+        residual_co2(conditioning.peak_temperature)? + VolumesCO2::new(co2 / CO2_PER_VOLUME)?;
+    let pressure = equilibrium_pressure(carbonation, storage_temperature).max(0.0);
+    Ok(BottlePressure {
+        carbonation,
+        pressure: Psi::new(pressure)?.into(),
+        exceeds_rating: carbonation > rating.max_carbonation(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::temperature::Temperature;
+    use crate::units::volume::Volume;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_residual_co2() {
+        assert_approx_eq!(
+            residual_co2(Celsius::new(20.0).unwrap()).unwrap().value,
+            0.861,
+            0.001
+        );
+        assert_approx_eq!(
+            residual_co2(Celsius::new(0.0).unwrap()).unwrap().value,
+            1.708,
+            0.001
+        );
+        assert_approx_eq!(
+            residual_co2(Celsius::new(30.0).unwrap()).unwrap().value,
+            0.697,
+            0.001
+        );
+        // Beyond the fit the parabola rises again with temperature
+        assert!(residual_co2(Celsius::new(30.5).unwrap()).is_err());
+        assert!(residual_co2(Celsius::new(50.0).unwrap()).is_err());
+        assert!(residual_co2(Celsius::new(-1.0).unwrap()).is_err());
+        assert!(residual_co2(Celsius::new(f32::MAX).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_priming_sugars() {
+        let volume = Litre::new(20.0).unwrap();
+        let temperature = Celsius::new(20.0).unwrap();
+        let target = VolumesCO2::new(2.5).unwrap();
+        let dextrose = priming_sugar(target, volume, temperature, PrimingSugar::Dextrose).unwrap();
+        assert_approx_eq!(dextrose.value, 138.5, 0.1);
+        let other = PrimingSugar::Other {
+            sucrose_equivalent: 0.5,
+        };
+        let other = priming_sugar(target, volume, temperature, other).unwrap();
+        assert_approx_eq!(other.value, 252.1, 0.1);
+    }
+
+    #[test]
+    fn test_priming_sugar_errors() {
+        let volume = Litre::new(20.0).unwrap();
+        let cold = Celsius::new(0.0).unwrap();
+        let low = VolumesCO2::new(1.5).unwrap();
+        assert!(priming_sugar(low, volume, cold, PrimingSugar::Sucrose).is_err());
+        let invalid = PrimingSugar::Other {
+            sucrose_equivalent: 0.0,
+        };
+        assert!(priming_sugar(VolumesCO2::new(2.5).unwrap(), volume, cold, invalid).is_err());
+    }
+
+    #[test]
+    fn test_force_carbonation() {
+        let fridge = Celsius::new(4.0).unwrap();
+        let target = VolumesCO2::new(2.4).unwrap();
+        let pressure = force_carbonation_pressure(target, fridge).unwrap();
+        assert_approx_eq!(Psi::from(pressure).value, 10.79, 0.01);
+        let volumes = force_carbonation_volumes(pressure, fridge).unwrap();
+        assert_approx_eq!(volumes.value, 2.4, 0.001);
+
+        let flat = VolumesCO2::new(1.0).unwrap();
+        assert!(force_carbonation_pressure(flat, fridge).is_err());
+    }
+
+    #[test]
+    fn test_carbonation_table() {
+        let table = carbonation_table(
+            Celsius::new(2.0).unwrap(),
+            Celsius::new(4.0).unwrap(),
+            TemperatureDifference::new(2.0).unwrap(),
+            VolumesCO2::new(1.0).unwrap(),
+            VolumesCO2::new(2.4).unwrap(),
+            VolumesCO2::new(1.4).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(table.pressures[0][0], None);
+        assert_eq!(
+            table.pressures[1][1],
+            force_carbonation_pressure(table.volumes[1], table.temperatures[1]).ok()
+        );
+        let text = table.format(UnitSystem::Us);
+        assert_eq!(text.lines().count(), 3);
+        assert!(text.contains("39.2 °F"));
+        assert!(text.contains("10.8 psi"));
+    }
+
+    #[test]
+    fn test_carbonation_table_ranges() {
+        let (first, last) = (VolumesCO2::new(2.0).unwrap(), VolumesCO2::new(3.0).unwrap());
+        let table = |step| {
+            carbonation_table(
+                Celsius::new(0.0).unwrap(),
+                Celsius::new(0.0).unwrap(),
+                TemperatureDifference::new(1.0).unwrap(),
+                first,
+                last,
+                VolumesCO2::new(step).unwrap(),
+            )
+        };
+        // 0.1 is not exact in binary, the last level is still included
+        assert_eq!(table(0.1).unwrap().volumes.len(), 11);
+        assert_eq!(table(0.3).unwrap().volumes.len(), 4);
+        assert!(table(0.0).is_err());
+        assert!(table(1e-30).is_err());
+        assert!(table(1e-6).is_err());
+        assert_eq!(table(0.002).unwrap().volumes.len(), 501);
+        let infinite = carbonation_table(
+            Celsius::new(0.0).unwrap(),
+            Celsius::new(0.0).unwrap(),
+            TemperatureDifference::new(1.0).unwrap(),
+            first,
+            VolumesCO2::new(f32::INFINITY).unwrap(),
+            VolumesCO2::new(0.1).unwrap(),
+        );
+        assert!(infinite.is_err());
+        let reversed = carbonation_table(
+            Celsius::new(4.0).unwrap(),
+            Celsius::new(2.0).unwrap(),
+            TemperatureDifference::new(1.0).unwrap(),
+            first,
+            last,
+            VolumesCO2::new(0.1).unwrap(),
+        );
+        assert!(reversed.is_err());
+    }
+
+    #[test]
+    fn test_bottle_pressure() {
+        let conditioning = BottleConditioning {
+            priming_sugar: Gram::new(126.0).unwrap(),
+            sugar: PrimingSugar::Sucrose,
+            beer_volume: Litre::new(20.0).unwrap(),
+            peak_temperature: Celsius::new(20.0).unwrap(),
+            residual_extract: None,
+        };
+        let storage = Celsius::new(20.0).unwrap();
+        let check = bottle_pressure(&conditioning, storage, BottleRating::Standard).unwrap();
+        assert_approx_eq!(check.carbonation.value, 2.5, 0.001);
+        assert_approx_eq!(Psi::from(check.pressure).value, 27.65, 0.01);
+        assert!(!check.exceeds_rating);
+
+        let cold = bottle_pressure(
+            &conditioning,
+            Celsius::new(4.0).unwrap(),
+            BottleRating::Standard,
+        )
+        .unwrap();
+        assert!(cold.pressure < check.pressure);
+
+        let empty = BottleConditioning {
+            beer_volume: Litre::new(0.0).unwrap(),
+            ..conditioning
+        };
+        assert!(bottle_pressure(&empty, storage, BottleRating::Standard).is_err());
+    }
+
+    #[test]
+    fn test_bottle_pressure_residual_extract() {
+        let storage = Celsius::new(20.0).unwrap();
+        let mut unfinished = BottleConditioning {
+            priming_sugar: Gram::new(126.0).unwrap(),
+            sugar: PrimingSugar::Sucrose,
+            beer_volume: Litre::new(20.0).unwrap(),
+            peak_temperature: Celsius::new(20.0).unwrap(),
+            residual_extract: Some(ResidualExtract {
+                current_gravity: SpecificGravity::new(1.015).unwrap(),
+                final_gravity: SpecificGravity::new(1.010).unwrap(),
+            }),
+        };
+        let check = bottle_pressure(&unfinished, storage, BottleRating::Standard).unwrap();
+        // 2.5 + 0.8192 * 1.265 °P * 10 * 1.015 * 0.9565 / 2.0665 / 1.977
+        assert_approx_eq!(check.carbonation.value, 4.96, 0.01);
+        assert!(check.exceeds_rating);
+        let check = bottle_pressure(&unfinished, storage, BottleRating::Belgian).unwrap();
+        assert!(check.exceeds_rating);
+        let check = bottle_pressure(&unfinished, storage, BottleRating::Champagne).unwrap();
+        assert!(!check.exceeds_rating);
+
+        // Below the final gravity there is nothing left to ferment
+        unfinished.residual_extract = Some(ResidualExtract {
+            current_gravity: SpecificGravity::new(1.008).unwrap(),
+            final_gravity: SpecificGravity::new(1.010).unwrap(),
+        });
+        let check = bottle_pressure(&unfinished, storage, BottleRating::Standard).unwrap();
+        assert_approx_eq!(check.carbonation.value, 2.5, 0.001);
+    }
+}
@@ -0,0 +1,273 @@
+//! Beer colour
+//!
+//! [Reference](https://beermaverick.com/understanding-srm-and-lovibond-beer-color-calculations/)
+//!
+//! - $m$ \[kg\]: Fermentable mass,
+//! - $L$ \[°L\]: Fermentable colour in degrees Lovibond, see [`units::colour`](crate::units::colour)
+//!   for conversions from EBC and SRM,
+//! - $V$ \[l\]: Wort volume,
+//! - $MCU$ \[-\]: Malt colour units,
+//! - $SRM$ \[-\]: Beer colour, standard reference method,
+//! - $d$ \[cm\]: Glass diameter.
+
+use crate::fermentable::GrainBill;
+use crate::units::colour::{Colour, Ebc, Srm};
+use crate::units::mass::KILOGRAMS_PER_POUND_F32;
+use crate::units::volume::{Litre, LITRES_PER_US_GALLON_F32};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// Conversion from °L kg / l to the customary °L lb / gal
+const MCU_METRIC_FACTOR: f32 = LITRES_PER_US_GALLON_F32 / KILOGRAMS_PER_POUND_F32;
+
+/// Malt colour units
+///
+/// $$
+///     MCU = \frac{\sum_i L_i m_i}{V} \cdot \frac{3.785}{0.4536}.
+/// $$
+/// The last factor converts from metric units to the customary °L lb / gal.
+pub fn malt_colour_units(grain_bill: &GrainBill, volume: Litre) -> f32 {
+    let colour_mass: f32 = grain_bill
+        .additions
+        .iter()
+        .map(|addition| addition.fermentable.colour.value * addition.mass)
+        .sum();
+    MCU_METRIC_FACTOR * colour_mass / volume.value
+}
+
+/// Beer colour from a grain bill
+///
+/// See [`Method`] for the available MCU to SRM conversions.
+///
+/// ```
+/// # use brew_calculator::colour::{srm, Method};
+/// # use brew_calculator::fermentable::*;
+/// # use brew_calculator::units::colour::{Colour, Ebc, Lovibond};
+/// # use brew_calculator::units::volume::{Litre, Volume};
+/// # use assert_approx_eq::assert_approx_eq;
+/// // Maltsters often give the colour in EBC
+/// let crystal = Fermentable {
+///     name: "Crystal 60".into(),
+///     fermentable_type: FermentableType::Grain,
+///     potential: Potential::Ppg(34.0),
+///     colour: Lovibond::from(Ebc::new(159.0).unwrap()),
+/// };
+/// let grain_bill = GrainBill {
+///     additions: vec![FermentableAddition {
+///         fermentable: crystal,
+///         mass: 0.5,
+///         timing: Timing::Mash,
+///     }],
+/// };
+/// let colour = srm(&grain_bill, Litre::new(20.0).unwrap(), Method::Morey);
+/// assert_approx_eq!(colour.value, 8.45, 0.01);
+/// ```
+pub fn srm(grain_bill: &GrainBill, volume: Litre, method: Method) -> Srm {
+    method.srm(malt_colour_units(grain_bill, volume))
+}
+
+/// MCU to SRM conversion
+///
+/// The MCU is a linear measure of the malt colour, while the beer colour saturates as the
+/// wort gets darker.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Method {
+    /// Morey, the most common, based on the other equations:
+    /// $$
+    ///     SRM = 1.4922 \cdot MCU^{0.6859}
+    /// $$
+    #[default]
+    Morey,
+    /// Daniels, for beers darker than $MCU \approx 11$:
+    /// $$
+    ///     SRM = 0.2 \cdot MCU + 8.4
+    /// $$
+    Daniels,
+    /// Mosher, for beers darker than $MCU \approx 7$:
+    /// $$
+    ///     SRM = 0.3 \cdot MCU + 4.7
+    /// $$
+    Mosher,
+}
+
+impl Method {
+    /// Beer colour $SRM$ from malt colour units
+    pub fn srm(self, malt_colour_units: f32) -> Srm {
+        let srm = match self {
+            Method::Morey => 1.4922 * malt_colour_units.powf(0.6859),
+            Method::Daniels => 0.2 * malt_colour_units + 8.4,
+            Method::Mosher => 0.3 * malt_colour_units + 4.7,
+        };
+        Srm::new(srm.max(0.0)).expect("Non-negative colour")
+    }
+}
+
+/// Glass diameter $d_{ref}$ \[cm\] that the colour lookup table is taken to be seen through
+pub const REFERENCE_GLASS_DIAMETER: f32 = 5.0;
+
+/// sRGB colour of beer at integer SRM from 1 to 40, the lookup table common in recipe tools
+const SRM_RGB: [(u8, u8, u8); 40] = [
+    (0xFF, 0xE6, 0x99),
+    (0xFF, 0xD8, 0x78),
+    (0xFF, 0xCA, 0x5A),
+    (0xFF, 0xBF, 0x42),
+    (0xFB, 0xB1, 0x23),
+    (0xF8, 0xA6, 0x00),
+    (0xF3, 0x9C, 0x00),
+    (0xEA, 0x8F, 0x00),
+    (0xE5, 0x85, 0x00),
+    (0xDE, 0x7C, 0x00),
+    (0xD7, 0x72, 0x00),
+    (0xCF, 0x69, 0x00),
+    (0xCB, 0x62, 0x00),
+    (0xC3, 0x59, 0x00),
+    (0xBB, 0x51, 0x00),
+    (0xB5, 0x4C, 0x00),
+    (0xB0, 0x45, 0x00),
+    (0xA6, 0x3E, 0x00),
+    (0xA1, 0x37, 0x00),
+    (0x9B, 0x32, 0x00),
+    (0x95, 0x2D, 0x00),
+    (0x8E, 0x29, 0x00),
+    (0x88, 0x23, 0x00),
+    (0x82, 0x1E, 0x00),
+    (0x7B, 0x1A, 0x00),
+    (0x77, 0x19, 0x00),
+    (0x70, 0x14, 0x00),
+    (0x6A, 0x0E, 0x00),
+    (0x66, 0x0D, 0x00),
+    (0x5E, 0x0B, 0x00),
+    (0x5A, 0x0A, 0x02),
+    (0x60, 0x09, 0x03),
+    (0x52, 0x09, 0x07),
+    (0x4C, 0x05, 0x05),
+    (0x47, 0x06, 0x06),
+    (0x44, 0x06, 0x07),
+    (0x3F, 0x07, 0x08),
+    (0x3B, 0x06, 0x07),
+    (0x3A, 0x07, 0x0B),
+    (0x36, 0x08, 0x0A),
+];
+
+/// sRGB colour with 8 bits per channel
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb {
+    /// Red channel
+    pub red: u8,
+    /// Green channel
+    pub green: u8,
+    /// Blue channel
+    pub blue: u8,
+}
+
+#[wasm_bindgen]
+impl Rgb {
+    /// Hex string, e.g. `"#DE7C00"`
+    pub fn hex(self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.red, self.green, self.blue)
+    }
+}
+
+/// Apparent colour of beer in a glass
+///
+/// By the Beer–Lambert law the absorbance is proportional to the path length through the
+/// beer, so the glass is accounted for by looking up the colour at
+/// $$
+///     SRM \cdot \frac{d}{d_{ref}}
+/// $$
+/// in the lookup table, with linear interpolation between the tabulated values.
+/// Colours outside the table are clamped to its ends.
+///
+/// ```
+/// # use brew_calculator::colour::{rgb, REFERENCE_GLASS_DIAMETER};
+/// # use brew_calculator::units::colour::{Colour, Srm};
+/// let pale_ale = Srm::new(10.0).unwrap();
+/// assert_eq!(rgb(pale_ale, REFERENCE_GLASS_DIAMETER).hex(), "#DE7C00");
+/// // A wider glass looks darker
+/// assert_eq!(rgb(pale_ale, 2.0 * REFERENCE_GLASS_DIAMETER).hex(), "#9B3200");
+/// ```
+pub fn rgb(colour: Srm, glass_diameter: f32) -> Rgb {
+    let position = colour.value * glass_diameter / REFERENCE_GLASS_DIAMETER - 1.0;
+    let last = SRM_RGB.len() - 1;
+    let position = position.max(0.0).min(last as f32);
+    let lower = position.floor() as usize;
+    let upper = (lower + 1).min(last);
+    let weight = position - lower as f32;
+    let interpolate = |low: u8, high: u8| -> u8 {
+        (f32::from(low) + weight * (f32::from(high) - f32::from(low))).round() as u8
+    };
+    let (low, high) = (SRM_RGB[lower], SRM_RGB[upper]);
+    Rgb {
+        red: interpolate(low.0, high.0),
+        green: interpolate(low.1, high.1),
+        blue: interpolate(low.2, high.2),
+    }
+}
+
+/// Hex colour from SRM, see [`rgb`]
+///
+/// Invalid colours are taken as $0$ SRM.
+#[wasm_bindgen]
+pub fn srm_to_hex(srm: f32, glass_diameter: f32) -> String {
+    rgb(
+        Srm::new(srm.max(0.0)).expect("Non-negative colour"),
+        glass_diameter,
+    )
+    .hex()
+}
+
+/// Hex colour from EBC, see [`rgb`]
+///
+/// Invalid colours are taken as $0$ EBC.
+#[wasm_bindgen]
+pub fn ebc_to_hex(ebc: f32, glass_diameter: f32) -> String {
+    let colour = Ebc::new(ebc.max(0.0)).expect("Non-negative colour");
+    rgb(colour.into(), glass_diameter).hex()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fermentable::{mashed_malt, Potential};
+    use crate::units::volume::Volume;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_malt_colour_units() {
+        // 10 lb of 2 °L and 1 lb of 40 °L in 5 gal gives MCU = 60 / 5 = 12
+        let grain_bill = GrainBill {
+            additions: vec![
+                mashed_malt(Potential::Ppg(36.0), 2.0, 4.535_924),
+                mashed_malt(Potential::Ppg(36.0), 40.0, 0.453_592),
+            ],
+        };
+        let mcu = malt_colour_units(&grain_bill, Litre::new(18.927_06).unwrap());
+        assert_approx_eq!(mcu, 12.0, 0.001);
+    }
+
+    #[test]
+    fn test_methods() {
+        assert_approx_eq!(Method::Morey.srm(12.0).value, 8.20, 0.01);
+        assert_approx_eq!(Method::Daniels.srm(12.0).value, 10.8, 0.001);
+        assert_approx_eq!(Method::Mosher.srm(12.0).value, 8.3, 0.001);
+        assert_eq!(Method::default(), Method::Morey);
+    }
+
+    #[test]
+    fn test_rgb() {
+        let halfway = rgb(Srm::new(1.5).unwrap(), REFERENCE_GLASS_DIAMETER);
+        assert_eq!(
+            halfway,
+            Rgb {
+                red: 0xFF,
+                green: 0xDF,
+                blue: 0x89
+            }
+        );
+        assert_eq!(srm_to_hex(0.0, REFERENCE_GLASS_DIAMETER), "#FFE699");
+        assert_eq!(srm_to_hex(80.0, REFERENCE_GLASS_DIAMETER), "#36080A");
+        assert_eq!(srm_to_hex(f32::NAN, REFERENCE_GLASS_DIAMETER), "#FFE699");
+        assert_eq!(ebc_to_hex(19.7, REFERENCE_GLASS_DIAMETER), "#DE7C00");
+    }
+}
@@ -19,7 +19,7 @@ impl Concentration for SpecificGravity {
         if value.is_sign_negative() {
             return Err(Error::ValueError(format!(
                 "Expected non-negative value, got: {}.",
-                value.to_string()
+                value
             )));
         }
         Ok(SpecificGravity { value })
@@ -40,7 +40,7 @@ impl Concentration for Plato {
         if value.is_sign_negative() {
             return Err(Error::ValueError(format!(
                 "Expected non-negative value, got: {}.",
-                value.to_string()
+                value
             )));
         }
         Ok(Plato { value })
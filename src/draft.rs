@@ -0,0 +1,144 @@
+//! Draft system balancing
+//!
+//! A balanced line uses up the serving pressure on the way to the tap, so the beer pours
+//! without foaming or dribbling:
+//!
+//! - $P$ \[bar\]: Serving pressure at the keg,
+//! - $h$ \[m\]: Rise from the keg to the tap, negative for a fall,
+//! - $r$ \[bar / m\]: Line resistance at the reference flow rate $Q_{ref}$,
+//! - $Q$ \[l / min\]: Flow rate at the tap,
+//! - $L$ \[m\]: Line length.
+
+use crate::units::dimension;
+use crate::units::pressure::Bar;
+use crate::units::volume::LITRES_PER_US_GALLON_F32;
+use serde::{Deserialize, Serialize};
+
+/// Flow rate $Q_{ref}$ \[l / min\] for the line resistances, one US gallon per minute
+pub const REFERENCE_FLOW_RATE: f32 = LITRES_PER_US_GALLON_F32;
+/// Hydrostatic pressure of the beer per metre of rise \[bar / m\], taking the density of
+/// beer as that of water
+const HYDROSTATIC_PRESSURE: f32 = 0.098_07;
+
+/// Beer line tubing
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub struct BeerLine {
+    /// Inner diameter \[mm\]
+    pub inner_diameter: f32,
+    /// Resistance $r$ \[bar / m\] at the reference flow rate, depends on both the diameter
+    /// and the material
+    pub resistance: f32,
+}
+
+impl BeerLine {
+    /// 3/16" vinyl, 3.0 psi / ft
+    pub const VINYL_3_16: BeerLine = BeerLine {
+        inner_diameter: 4.763,
+        resistance: 0.678_6,
+    };
+    /// 1/4" vinyl, 0.85 psi / ft
+    pub const VINYL_1_4: BeerLine = BeerLine {
+        inner_diameter: 6.35,
+        resistance: 0.192_3,
+    };
+    /// 5/16" vinyl, 0.40 psi / ft
+    pub const VINYL_5_16: BeerLine = BeerLine {
+        inner_diameter: 7.938,
+        resistance: 0.090_48,
+    };
+    /// 3/8" vinyl, 0.20 psi / ft
+    pub const VINYL_3_8: BeerLine = BeerLine {
+        inner_diameter: 9.525,
+        resistance: 0.045_24,
+    };
+
+    /// Volume of beer \[l\] held by a line of length $L$ \[m\]
+    pub fn volume(&self, length: f32) -> f32 {
+        // In decimetres, to get litres
+        let radius = self.inner_diameter / 200.0;
+        std::f32::consts::PI * radius.powi(2) * length * 10.0
+    }
+}
+
+/// Line length for a balanced draft system
+///
+/// The resistance is taken to scale linearly with the flow rate,
+/// $$
+///     L = \frac{P - 0.0981 h}{r Q / Q_{ref}},
+/// $$
+/// where $0.0981$ bar / m is the pressure needed to lift the beer.
+/// The serving pressure for a carbonation level is given by
+/// [`force_carbonation_pressure`](crate::carbonation::force_carbonation_pressure).
+///
+/// ```
+/// # use brew_calculator::draft::{balanced_line_length, BeerLine, REFERENCE_FLOW_RATE};
+/// # use brew_calculator::units::pressure::{Bar, Pressure, Psi};
+/// # use assert_approx_eq::assert_approx_eq;
+/// let serving_pressure: Bar = Psi::new(12.0).unwrap().into();
+/// let length =
+///     balanced_line_length(serving_pressure, &BeerLine::VINYL_3_16, 0.0, REFERENCE_FLOW_RATE)
+///         .unwrap();
+/// assert_approx_eq!(length, 1.22, 0.01);
+/// ```
+pub fn balanced_line_length(
+    serving_pressure: Bar,
+    line: &BeerLine,
+    rise: f32,
+    flow_rate: f32,
+) -> Result<f32, dimension::Error> {
+    if flow_rate.is_nan() || flow_rate <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected positive flow rate, got: {}.",
+            flow_rate
+        )));
+    }
+    if line.resistance.is_nan() || line.resistance <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected positive line resistance, got: {}.",
+            line.resistance
+        )));
+    }
+    let line_pressure = serving_pressure.value - HYDROSTATIC_PRESSURE * rise;
+    if line_pressure.is_nan() || line_pressure <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Serving pressure {} cannot lift the beer {} m.",
+            serving_pressure, rise
+        )));
+    }
+    Ok(line_pressure / (line.resistance * flow_rate / REFERENCE_FLOW_RATE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::pressure::Pressure;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_balanced_line_length() {
+        let pressure = Bar::new(0.8).unwrap();
+        let line = BeerLine::VINYL_3_16;
+        let level = balanced_line_length(pressure, &line, 0.0, REFERENCE_FLOW_RATE).unwrap();
+        assert_approx_eq!(level, 1.179, 0.001);
+        let rise = balanced_line_length(pressure, &line, 1.0, REFERENCE_FLOW_RATE).unwrap();
+        assert_approx_eq!(rise, 1.034, 0.001);
+        let fall = balanced_line_length(pressure, &line, -1.0, REFERENCE_FLOW_RATE).unwrap();
+        assert_approx_eq!(fall, 1.323, 0.001);
+        let slow = balanced_line_length(pressure, &line, 0.0, REFERENCE_FLOW_RATE / 2.0).unwrap();
+        assert_approx_eq!(slow, 2.0 * level, 0.001);
+    }
+
+    #[test]
+    fn test_unbalanced() {
+        let pressure = Bar::new(0.5).unwrap();
+        let line = BeerLine::VINYL_1_4;
+        assert!(balanced_line_length(pressure, &line, 6.0, 3.0).is_err());
+        assert!(balanced_line_length(pressure, &line, 0.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_line_volume() {
+        // 3/16" holds about 18 ml per metre
+        assert_approx_eq!(BeerLine::VINYL_3_16.volume(1.0), 0.0178, 0.0001);
+    }
+}
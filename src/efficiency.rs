@@ -0,0 +1,243 @@
+//! Brewhouse efficiencies from brew day measurements
+//!
+//! The efficiencies compare the extract measured at each stage with the potential extract of the
+//! grain bill.
+//!
+//! - $E_{pot}$ \[pt l\]: Potential extract of the grain bill, see [`GrainBill`],
+//! - $V$ \[l\]: Measured wort volume,
+//! - $\rho$ \[-\]: Measured wort gravity,
+//! - $P$ \[°P\]: Measured wort extract concentration.
+//!
+//! All efficiencies are in percentage, not fraction.
+
+use crate::fermentable::{FermentableAddition, GrainBill, Timing};
+use crate::units::concentration::{Plato, SpecificGravity};
+use crate::units::dimension;
+use crate::units::extract::GravityPoints;
+use crate::units::volume::Litre;
+use crate::units::{Kilograms, Percent};
+use serde::{Deserialize, Serialize};
+
+/// Volume and gravity of a wort sample
+#[derive(Debug, Clone, Copy)]
+pub struct WortMeasurement {
+    /// Wort volume $V$ \[l\]
+    pub volume: Litre,
+    /// Wort gravity $\rho$ \[-\]
+    pub gravity: SpecificGravity,
+}
+
+impl WortMeasurement {
+    /// Extract in the measured wort
+    pub fn extract(self) -> GravityPoints {
+        GravityPoints::from_gravity(self.gravity, self.volume)
+    }
+}
+
+/// Measurements from a brew day
+#[derive(Debug, Clone, Copy)]
+pub struct BrewDayMeasurements {
+    /// Strike water volume $V_{strike}$ \[l\]
+    pub strike_water: Litre,
+    /// Gravity of the first wort, drawn from the mash before sparging
+    pub first_wort_gravity: SpecificGravity,
+    /// Collected wort before the boil
+    pub pre_boil: WortMeasurement,
+    /// Wort transferred to the fermenter
+    pub fermenter: WortMeasurement,
+}
+
+/// Efficiencies for a brew day
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub struct Efficiencies {
+    /// See [`conversion_efficiency`]
+    pub conversion: Percent,
+    /// See [`mash_efficiency`]
+    pub mash: Percent,
+    /// See [`brewhouse_efficiency`]
+    pub brewhouse: Percent,
+}
+
+/// All efficiencies for a brew day
+pub fn efficiencies(
+    grain_bill: &GrainBill,
+    measurements: &BrewDayMeasurements,
+) -> Result<Efficiencies, dimension::Error> {
+    Ok(Efficiencies {
+        conversion: conversion_efficiency(
+            grain_bill,
+            measurements.strike_water,
+            measurements.first_wort_gravity,
+        )?,
+        mash: mash_efficiency(grain_bill, measurements.pre_boil)?,
+        brewhouse: brewhouse_efficiency(grain_bill, measurements.fermenter)?,
+    })
+}
+
+/// Conversion efficiency $\eta_{conv}$ \[-\]
+///
+/// [Reference](http://braukaiser.com/wiki/index.php/Understanding_Efficiency)
+///
+/// The share of the potential extract of the mashed fermentables that was converted to
+/// soluble extract in the mash.
+/// The extract mass in the first wort is estimated from its concentration and the strike
+/// water mass (assuming $1$ kg/l), neglecting the grain moisture:
+/// $$
+///     \eta_{conv} = 100 \frac{V_{strike} \frac{P_{fw}}{100 - P_{fw}}}{\sum_i m_i x_i},
+/// $$
+/// where $x_i$ \[-\] is the extract yield fraction of the fermentable.
+pub fn conversion_efficiency(
+    grain_bill: &GrainBill,
+    strike_water: Litre,
+    first_wort_gravity: SpecificGravity,
+) -> Result<Percent, dimension::Error> {
+    let potential_extract_mass: Kilograms = mashed(grain_bill)
+        .map(|addition| addition.mass * addition.fermentable.potential.extract_fraction())
+        .sum();
+    let first_wort: Plato = first_wort_gravity.into();
+    let extract_mass = strike_water.value * first_wort.value / (100.0 - first_wort.value);
+    ratio(extract_mass, potential_extract_mass)
+}
+
+/// Mash efficiency $\eta_{mash}$ \[-\]
+///
+/// The share of the potential extract of the mashed fermentables that is collected in the
+/// kettle before the boil.
+/// Non-mashed fermentables added to the mash, e.g. steeped extract, are subtracted from the
+/// measured extract:
+/// $$
+///     \eta_{mash} = 100 \frac{E_{pre} - E_{steep}}{E_{pot, mash}}
+/// $$
+pub fn mash_efficiency(
+    grain_bill: &GrainBill,
+    pre_boil: WortMeasurement,
+) -> Result<Percent, dimension::Error> {
+    let potential = sum_extract(mashed(grain_bill));
+    let steeped = sum_extract(grain_bill.additions.iter().filter(|addition| {
+        addition.timing == Timing::Mash && !addition.fermentable.fermentable_type.is_mashed()
+    }));
+    ratio(pre_boil.extract().value - steeped, potential)
+}
+
+/// Brewhouse efficiency $\eta_{bh}$ \[-\]
+///
+/// The share of the potential extract of the whole grain bill that reaches the fermenter:
+/// $$
+///     \eta_{bh} = 100 \frac{E_{ferm}}{E_{pot}}
+/// $$
+///
+/// ```
+/// # use brew_calculator::efficiency::{brewhouse_efficiency, WortMeasurement};
+/// # use brew_calculator::fermentable::*;
+/// # use brew_calculator::units::colour::{Colour, Lovibond};
+/// # use brew_calculator::units::concentration::{Concentration, SpecificGravity};
+/// # use brew_calculator::units::volume::{Litre, Volume};
+/// # use assert_approx_eq::assert_approx_eq;
+/// let grain_bill = GrainBill {
+///     additions: vec![FermentableAddition {
+///         fermentable: Fermentable {
+///             name: "Pale malt".into(),
+///             fermentable_type: FermentableType::Grain,
+///             potential: Potential::Ppg(37.0),
+///             colour: Lovibond::new(3.0).unwrap(),
+///         },
+///         mass: 5.0,
+///         timing: Timing::Mash,
+///     }],
+/// };
+/// let fermenter = WortMeasurement {
+///     volume: Litre::new(20.0).unwrap(),
+///     gravity: SpecificGravity::new(1.054).unwrap(),
+/// };
+/// let efficiency = brewhouse_efficiency(&grain_bill, fermenter).unwrap();
+/// assert_approx_eq!(efficiency, 69.96, 0.05);
+/// ```
+pub fn brewhouse_efficiency(
+    grain_bill: &GrainBill,
+    fermenter: WortMeasurement,
+) -> Result<Percent, dimension::Error> {
+    ratio(
+        fermenter.extract().value,
+        grain_bill.potential_extract().value,
+    )
+}
+
+fn mashed(grain_bill: &GrainBill) -> impl Iterator<Item = &FermentableAddition> {
+    grain_bill
+        .additions
+        .iter()
+        .filter(|addition| addition.fermentable.fermentable_type.is_mashed())
+}
+
+fn sum_extract<'a, I>(additions: I) -> f32
+where
+    I: Iterator<Item = &'a FermentableAddition>,
+{
+    additions
+        .map(|addition| addition.extract(100.0).value)
+        .sum()
+}
+
+fn ratio(measured: f32, potential: f32) -> Result<Percent, dimension::Error> {
+    if potential <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected positive potential extract, got: {}.",
+            potential
+        )));
+    }
+    Ok(100.0 * measured / potential)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fermentable::{mashed_malt, Potential};
+    use crate::units::concentration::Concentration;
+    use crate::units::volume::Volume;
+    use assert_approx_eq::assert_approx_eq;
+
+    fn grain_bill() -> GrainBill {
+        GrainBill {
+            additions: vec![mashed_malt(Potential::Extract(80.0), 2.0, 5.0)],
+        }
+    }
+
+    #[test]
+    fn test_conversion_efficiency() {
+        // 15 l strike water at 20.3 °P holds 15 * 20.3 / 79.7 = 3.82 kg extract,
+        // out of a potential of 5 kg * 0.8 = 4 kg.
+        let first_wort: SpecificGravity = Plato::new(20.3).unwrap().into();
+        let efficiency =
+            conversion_efficiency(&grain_bill(), Litre::new(15.0).unwrap(), first_wort).unwrap();
+        assert_approx_eq!(efficiency, 95.5, 0.5);
+    }
+
+    #[test]
+    fn test_mash_and_brewhouse_efficiency() {
+        let measurements = BrewDayMeasurements {
+            strike_water: Litre::new(15.0).unwrap(),
+            first_wort_gravity: Plato::new(20.3).unwrap().into(),
+            pre_boil: WortMeasurement {
+                volume: Litre::new(28.0).unwrap(),
+                gravity: SpecificGravity::new(1.040).unwrap(),
+            },
+            fermenter: WortMeasurement {
+                volume: Litre::new(21.0).unwrap(),
+                gravity: SpecificGravity::new(1.048).unwrap(),
+            },
+        };
+        let potential = 5.0 * 0.8 * 46.214 * 8.3454;
+        let efficiencies = efficiencies(&grain_bill(), &measurements).unwrap();
+        assert_approx_eq!(efficiencies.mash, 100.0 * 40.0 * 28.0 / potential, 0.1);
+        assert_approx_eq!(efficiencies.brewhouse, 100.0 * 48.0 * 21.0 / potential, 0.1);
+    }
+
+    #[test]
+    fn test_empty_grain_bill() {
+        let fermenter = WortMeasurement {
+            volume: Litre::new(20.0).unwrap(),
+            gravity: SpecificGravity::new(1.050).unwrap(),
+        };
+        assert!(brewhouse_efficiency(&GrainBill::default(), fermenter).is_err());
+    }
+}
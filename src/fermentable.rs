@@ -0,0 +1,315 @@
+//! Fermentables and gravity prediction
+//!
+//! - $m$ \[kg\]: Fermentable mass,
+//! - $p$ \[pt l / kg\]: Extract potential, gravity points per kg in one litre,
+//! - $\eta$ \[-\]: Mash efficiency (percentage not fraction),
+//! - $V$ \[l\]: Wort volume,
+//! - $\rho$ \[-\]: Wort gravity.
+
+use crate::units::colour::Lovibond;
+use crate::units::concentration::SpecificGravity;
+use crate::units::dimension;
+use crate::units::extract::GravityPoints;
+use crate::units::mass::KILOGRAMS_PER_POUND_F32;
+use crate::units::volume::{Litre, LITRES_PER_US_GALLON_F32};
+use crate::units::{Kilograms, Percent};
+use serde::{Deserialize, Serialize};
+
+/// Potential of pure sucrose in points per pound per gallon (PPG)
+const SUCROSE_PPG: f32 = 46.214;
+
+/// Kind of fermentable
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+pub enum FermentableType {
+    /// Malted grain, e.g. pale malt
+    Grain,
+    /// Unmalted grain or flakes, converted by the malt enzymes in the mash
+    Adjunct,
+    /// Liquid malt extract (LME)
+    Extract,
+    /// Dry malt extract (DME)
+    DryExtract,
+    /// Sugar, syrup or honey
+    Sugar,
+}
+
+impl FermentableType {
+    /// Whether the extract has to be converted and lautered in the mash.
+    ///
+    /// Only mashed fermentables are subject to the mash efficiency,
+    /// the others yield their full potential.
+    pub fn is_mashed(self) -> bool {
+        match self {
+            FermentableType::Grain | FermentableType::Adjunct => true,
+            FermentableType::Extract | FermentableType::DryExtract | FermentableType::Sugar => {
+                false
+            }
+        }
+    }
+}
+
+/// Extract potential of a fermentable
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub enum Potential {
+    /// Gravity points per pound per US gallon
+    Ppg(f32),
+    /// Extract yield in percent of the mass (fine grind, dry basis)
+    Extract(Percent),
+}
+
+impl Potential {
+    /// Potential $p$ in gravity points per kg in one litre \[pt l / kg\].
+    ///
+    /// $$
+    ///     p = PPG \cdot \frac{3.785}{0.4536}
+    /// $$
+    /// Extract percentages are relative to pure sucrose, $46.214$ PPG.
+    pub fn points(self) -> f32 {
+        self.ppg() * LITRES_PER_US_GALLON_F32 / KILOGRAMS_PER_POUND_F32
+    }
+
+    /// Potential in points per pound per US gallon
+    pub fn ppg(self) -> f32 {
+        match self {
+            Potential::Ppg(ppg) => ppg,
+            Potential::Extract(extract) => extract / 100.0 * SUCROSE_PPG,
+        }
+    }
+
+    /// Extract yield as a fraction of the mass \[-\]
+    pub fn extract_fraction(self) -> f32 {
+        self.ppg() / SUCROSE_PPG
+    }
+}
+
+/// A fermentable ingredient
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+pub struct Fermentable {
+    /// Name, e.g. "Pilsner malt"
+    pub name: String,
+    /// Kind of fermentable
+    pub fermentable_type: FermentableType,
+    /// Extract potential
+    pub potential: Potential,
+    /// Colour, specifications in EBC or SRM convert into degrees Lovibond
+    pub colour: Lovibond,
+}
+
+/// When a fermentable is added
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Timing {
+    /// Added to the mash (or steeped), contributes to the pre-boil gravity
+    #[default]
+    Mash,
+    /// Added to the boil, only contributes to the original gravity
+    Boil,
+}
+
+/// A fermentable with its mass in a recipe
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+pub struct FermentableAddition {
+    /// The fermentable
+    pub fermentable: Fermentable,
+    /// Mass $m$ \[kg\]
+    pub mass: Kilograms,
+    /// When it is added
+    #[serde(default)]
+    pub timing: Timing,
+}
+
+impl FermentableAddition {
+    /// Extract yielded by the addition
+    ///
+    /// $$
+    ///     E = m p \frac{\eta}{100},
+    /// $$
+    /// where $\eta = 100$ for fermentables that are not mashed.
+    pub fn extract(&self, mash_efficiency: Percent) -> GravityPoints {
+        let efficiency = if self.fermentable.fermentable_type.is_mashed() {
+            mash_efficiency / 100.0
+        } else {
+            1.0
+        };
+        let points = self.mass * self.fermentable.potential.points() * efficiency;
+        // `max` also maps NaN to zero, so the extract is always valid.
+        GravityPoints::new(points.max(0.0)).expect("Non-negative extract")
+    }
+}
+
+/// All fermentables in a recipe
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+pub struct GrainBill {
+    /// Fermentable additions
+    pub additions: Vec<FermentableAddition>,
+}
+
+impl GrainBill {
+    /// Total mass of the mashed fermentables \[kg\]
+    pub fn mashed_mass(&self) -> Kilograms {
+        self.additions
+            .iter()
+            .filter(|addition| addition.fermentable.fermentable_type.is_mashed())
+            .map(|addition| addition.mass)
+            .sum()
+    }
+
+    /// Extract the grain bill would yield at a perfect (100 %) efficiency
+    pub fn potential_extract(&self) -> GravityPoints {
+        self.extract(100.0, |_| true)
+    }
+
+    /// Extract yielded by the additions in the mash at a given mash efficiency
+    pub fn mash_extract(&self, mash_efficiency: Percent) -> GravityPoints {
+        self.extract(mash_efficiency, |addition| addition.timing == Timing::Mash)
+    }
+
+    /// Extract yielded by all additions at a given mash efficiency
+    pub fn total_extract(&self, mash_efficiency: Percent) -> GravityPoints {
+        self.extract(mash_efficiency, |_| true)
+    }
+
+    fn extract<F>(&self, mash_efficiency: Percent, include: F) -> GravityPoints
+    where
+        F: Fn(&FermentableAddition) -> bool,
+    {
+        self.additions
+            .iter()
+            .filter(|addition| include(addition))
+            .map(|addition| addition.extract(mash_efficiency))
+            .fold(zero_points(), |total, extract| total + extract)
+    }
+}
+
+/// Predicted pre-boil gravity
+///
+/// Only fermentables added to the mash are included.
+///
+/// $$
+///     \rho_{pre} = 1 + \frac{\sum_i m_i p_i \eta_i}{100 \cdot 1000 V_{pre}}
+/// $$
+pub fn pre_boil_gravity(
+    grain_bill: &GrainBill,
+    mash_efficiency: Percent,
+    pre_boil_volume: Litre,
+) -> Result<SpecificGravity, dimension::Error> {
+    grain_bill
+        .mash_extract(mash_efficiency)
+        .gravity(pre_boil_volume)
+}
+
+/// Predicted original gravity (OG)
+///
+/// The extract from the mash is conserved through the boil,
+/// to which the boil additions are added.
+///
+/// ```
+/// # use brew_calculator::fermentable::*;
+/// # use brew_calculator::units::colour::{Colour, Lovibond};
+/// # use brew_calculator::units::volume::{Litre, Volume};
+/// # use assert_approx_eq::assert_approx_eq;
+/// let pale_malt = Fermentable {
+///     name: "Pale malt".into(),
+///     fermentable_type: FermentableType::Grain,
+///     potential: Potential::Ppg(37.0),
+///     colour: Lovibond::new(3.0).unwrap(),
+/// };
+/// let grain_bill = GrainBill {
+///     additions: vec![FermentableAddition {
+///         fermentable: pale_malt,
+///         mass: 4.5,
+///         timing: Timing::Mash,
+///     }],
+/// };
+/// let og = original_gravity(&grain_bill, 75.0, Litre::new(23.0).unwrap()).unwrap();
+/// assert_approx_eq!(og.value, 1.0453, 0.0005);
+/// ```
+pub fn original_gravity(
+    grain_bill: &GrainBill,
+    mash_efficiency: Percent,
+    post_boil_volume: Litre,
+) -> Result<SpecificGravity, dimension::Error> {
+    grain_bill
+        .total_extract(mash_efficiency)
+        .gravity(post_boil_volume)
+}
+
+fn zero_points() -> GravityPoints {
+    GravityPoints::new(0.0).expect("Zero is a valid extract")
+}
+
+/// Malt added to the mash, for tests
+#[cfg(test)]
+pub(crate) fn mashed_malt(
+    potential: Potential,
+    colour: f32,
+    mass: Kilograms,
+) -> FermentableAddition {
+    use crate::units::colour::Colour;
+    FermentableAddition {
+        fermentable: Fermentable {
+            name: "Malt".into(),
+            fermentable_type: FermentableType::Grain,
+            potential,
+            colour: Lovibond::new(colour).unwrap(),
+        },
+        mass,
+        timing: Timing::Mash,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::colour::Colour;
+    use crate::units::volume::Volume;
+    use assert_approx_eq::assert_approx_eq;
+
+    fn grain_bill() -> GrainBill {
+        let dextrose = Fermentable {
+            name: "Dextrose".into(),
+            fermentable_type: FermentableType::Sugar,
+            potential: Potential::Extract(91.0),
+            colour: Lovibond::new(0.0).unwrap(),
+        };
+        GrainBill {
+            additions: vec![
+                mashed_malt(Potential::Ppg(37.0), 3.0, 4.5),
+                FermentableAddition {
+                    fermentable: dextrose,
+                    mass: 0.5,
+                    timing: Timing::Boil,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_potential_conversion() {
+        assert_approx_eq!(Potential::Ppg(1.0).points(), 8.3454, 0.001);
+        assert_approx_eq!(Potential::Extract(80.0).ppg(), 36.97, 0.01);
+        assert_approx_eq!(Potential::Ppg(46.214).extract_fraction(), 1.0, 0.0001);
+    }
+
+    #[test]
+    fn test_pre_boil_gravity() {
+        let sg = pre_boil_gravity(&grain_bill(), 75.0, Litre::new(27.0).unwrap()).unwrap();
+        // 4.5 kg * 37 * 8.3454 pt l/kg * 0.75 / 27 l
+        assert_approx_eq!(sg.value, 1.03860, 0.0001);
+    }
+
+    #[test]
+    fn test_original_gravity() {
+        let og = original_gravity(&grain_bill(), 75.0, Litre::new(23.0).unwrap()).unwrap();
+        // Mash extract plus 0.5 kg dextrose at full yield.
+        let expected = 1.0 + (1042.13 + 0.5 * 0.91 * 46.214 * 8.3454) / (1000.0 * 23.0);
+        assert_approx_eq!(og.value, expected, 0.0001);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let grain_bill = grain_bill();
+        let json = serde_json::to_string(&grain_bill).unwrap();
+        let parsed: GrainBill = serde_json::from_str(&json).unwrap();
+        assert_eq!(grain_bill, parsed);
+    }
+}
@@ -6,7 +6,8 @@
 //! - $t$ \[min\]: Boil time
 //! - $\rho$ \[-\]: Wort gravity.
 
-use crate::units::{Ibu, Kilograms, Liters, Minutes, Percent};
+use crate::units::mass::Kilogram;
+use crate::units::{Ibu, Liters, Minutes, Percent};
 use serde::{Deserialize, Serialize};
 
 /// IBU for a single hop addition
@@ -14,7 +15,7 @@ pub trait IbuCalc {
     /// IBU for a single hop addition
     fn ibu(
         self,
-        hop_mass: Kilograms,
+        hop_mass: Kilogram,
         alpha_acid: Percent,
         volume: Liters,
         boil_time: Minutes,
@@ -31,15 +32,18 @@ pub trait IbuCalc {
 pub enum Method {
     /// See [`Tinseth`]
     Tinseth(Tinseth),
+    /// See [`rager_ibu`]
     Rager,
+    /// See [`garetz_ibu`]
     Garetz,
+    /// See [`noonan_ibu`]
     Noonan,
 }
 
 impl IbuCalc for Method {
     fn ibu(
         self,
-        hop_mass: Kilograms,
+        hop_mass: Kilogram,
         alpha_acid: Percent,
         volume: Liters,
         boil_time: Minutes,
@@ -77,7 +81,7 @@ pub struct Tinseth {}
 impl IbuCalc for Tinseth {
     fn ibu(
         self,
-        hop_mass: Kilograms,
+        hop_mass: Kilogram,
         alpha_acid: Percent,
         volume: Liters,
         boil_time: Minutes,
@@ -85,7 +89,8 @@ impl IbuCalc for Tinseth {
     ) -> Ibu {
         // The original formula has a factor 1000, however here `hop_mass` is measured in kg
         // and the `alpha_acid` in percentage, not a fraction.
-        let numerator = 10_000.0 * hop_mass * utilisation(boil_time, wort_gravity) * alpha_acid;
+        let numerator =
+            10_000.0 * hop_mass.value * utilisation(boil_time, wort_gravity) * alpha_acid;
         let denominator = volume * gravity_correction_factor(wort_gravity);
         numerator / denominator
     }
@@ -95,11 +100,11 @@ impl IbuCalc for Tinseth {
 ///
 /// TODO: Docs should look like `tinset_ibu`
 pub fn rager_ibu(
-    hop_mass: Kilograms,
-    alpha_acid: Percent,
-    volume: Liters,
-    boil_time: Minutes,
-    wort_gravity: f32,
+    _hop_mass: Kilogram,
+    _alpha_acid: Percent,
+    _volume: Liters,
+    _boil_time: Minutes,
+    _wort_gravity: f32,
 ) -> f32 {
     todo!();
 }
@@ -108,11 +113,11 @@ pub fn rager_ibu(
 ///
 /// TODO: Docs should look like `tinset_ibu`
 pub fn garetz_ibu(
-    hop_mass: Kilograms,
-    alpha_acid: Percent,
-    volume: Liters,
-    boil_time: Minutes,
-    wort_gravity: f32,
+    _hop_mass: Kilogram,
+    _alpha_acid: Percent,
+    _volume: Liters,
+    _boil_time: Minutes,
+    _wort_gravity: f32,
 ) -> f32 {
     todo!();
 }
@@ -121,11 +126,11 @@ pub fn garetz_ibu(
 ///
 /// TODO: Docs should look like `tinset_ibu`
 pub fn noonan_ibu(
-    hop_mass: Kilograms,
-    alpha_acid: Percent,
-    volume: Liters,
-    boil_time: Minutes,
-    wort_gravity: f32,
+    _hop_mass: Kilogram,
+    _alpha_acid: Percent,
+    _volume: Liters,
+    _boil_time: Minutes,
+    _wort_gravity: f32,
 ) -> f32 {
     todo!();
 }
@@ -173,6 +178,7 @@ pub fn gravity_correction_factor(gravity: f32) -> f32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::units::mass::{Mass, Ounce};
     use assert_approx_eq::assert_approx_eq;
 
     // https://www.backtoschoolbrewing.com/blog/2016/9/5/how-to-calculate-ibus
@@ -195,7 +201,15 @@ mod tests {
     // https://www.backtoschoolbrewing.com/blog/2016/9/5/how-to-calculate-ibus
     #[test]
     fn test_tinseth_ibu() {
-        let calc_ibu = Tinseth {}.ibu(0.007, 8.5, 22.73, 15.0, 1.058);
+        let hop_mass = Kilogram::new(0.007).unwrap();
+        let calc_ibu = Tinseth {}.ibu(hop_mass, 8.5, 22.73, 15.0, 1.058);
         assert_approx_eq!(calc_ibu, 2.74, 0.05);
     }
+
+    #[test]
+    fn test_tinseth_ibu_imperial_mass() {
+        let hop_mass = Ounce::new(0.25).unwrap();
+        let calc_ibu = Method::default().ibu(hop_mass.into(), 8.5, 22.73, 15.0, 1.058);
+        assert_approx_eq!(calc_ibu, 2.77, 0.05);
+    }
 }
@@ -39,8 +39,15 @@
 #![cfg_attr(feature = "clippy", warn(unseparated_literal_suffix))]
 #![cfg_attr(feature = "clippy", warn(wrong_pub_self_convention))]
 pub mod calculations;
+pub mod carbonation;
+pub mod colour;
+pub mod draft;
+pub mod efficiency;
+pub mod fermentable;
 pub mod ibu;
 // pub mod ph;
+#[allow(dead_code)]
 mod concentration;
 pub mod units;
 pub mod utils;
+pub mod yeast;
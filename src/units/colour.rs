@@ -0,0 +1,185 @@
+//! Colour units
+//!
+//! Beer colour is given in [`Srm`] in the US and [`Ebc`] in Europe,
+//! while maltsters specify grain colour in [`Ebc`] or degrees [`Lovibond`].
+//!
+//! [Reference](https://beermaverick.com/understanding-srm-and-lovibond-beer-color-calculations/)
+//!
+//! $$
+//!     EBC = 1.97 \cdot SRM, \quad SRM = 1.3546 \cdot L - 0.76.
+//! $$
+//! The Lovibond relation is empirical and gives negative SRM values below $L \approx 0.56$,
+//! such colours are converted to $0$ SRM.
+use crate::units::dimension;
+use std::f32;
+
+/// EBC per SRM
+const EBC_PER_SRM: f32 = 1.97;
+/// SRM per °L
+const SRM_PER_LOVIBOND: f32 = 1.3546;
+/// SRM offset of the Lovibond relation
+const SRM_LOVIBOND_OFFSET: f32 = 0.76;
+
+/// Colour quantity
+pub trait Colour: dimension::Dimension + Sized {
+    /// Validated construction, the value must be non-negative
+    fn new(value: f32) -> Result<Self, dimension::Error>;
+}
+
+fn validate(value: f32) -> Result<(), dimension::Error> {
+    if value.is_nan() {
+        return Err(dimension::Error::ValueError("NaN value".into()));
+    }
+    if value.is_sign_negative() {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected non-negative value, got: {}.",
+            value
+        )));
+    }
+    Ok(())
+}
+
+/// Standard reference method \[SRM\]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Srm {
+    /// Colour in SRM
+    pub value: f32,
+    _secret: (),
+}
+
+impl Colour for Srm {
+    fn new(value: f32) -> Result<Srm, dimension::Error> {
+        validate(value)?;
+        Ok(Srm { value, _secret: () })
+    }
+}
+
+/// European Brewery Convention \[EBC\]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Ebc {
+    /// Colour in EBC
+    pub value: f32,
+    _secret: (),
+}
+
+impl Colour for Ebc {
+    fn new(value: f32) -> Result<Ebc, dimension::Error> {
+        validate(value)?;
+        Ok(Ebc { value, _secret: () })
+    }
+}
+
+/// Degrees Lovibond \[°L\]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Lovibond {
+    /// Colour in degrees Lovibond
+    pub value: f32,
+    _secret: (),
+}
+
+impl Colour for Lovibond {
+    fn new(value: f32) -> Result<Lovibond, dimension::Error> {
+        validate(value)?;
+        Ok(Lovibond { value, _secret: () })
+    }
+}
+
+impl From<Srm> for Ebc {
+    fn from(colour: Srm) -> Ebc {
+        Ebc {
+            value: colour.value * EBC_PER_SRM,
+            _secret: (),
+        }
+    }
+}
+
+impl From<Ebc> for Srm {
+    fn from(colour: Ebc) -> Srm {
+        Srm {
+            value: colour.value / EBC_PER_SRM,
+            _secret: (),
+        }
+    }
+}
+
+impl From<Lovibond> for Srm {
+    fn from(colour: Lovibond) -> Srm {
+        Srm {
+            value: (SRM_PER_LOVIBOND * colour.value - SRM_LOVIBOND_OFFSET).max(0.0),
+            _secret: (),
+        }
+    }
+}
+
+impl From<Srm> for Lovibond {
+    fn from(colour: Srm) -> Lovibond {
+        Lovibond {
+            value: (colour.value + SRM_LOVIBOND_OFFSET) / SRM_PER_LOVIBOND,
+            _secret: (),
+        }
+    }
+}
+
+impl From<Lovibond> for Ebc {
+    fn from(colour: Lovibond) -> Ebc {
+        Srm::from(colour).into()
+    }
+}
+
+impl From<Ebc> for Lovibond {
+    fn from(colour: Ebc) -> Lovibond {
+        Srm::from(colour).into()
+    }
+}
+
+impl dimension::Dimension for Srm {
+    fn value(self) -> f32 {
+        self.value
+    }
+}
+
+impl dimension::Dimension for Ebc {
+    fn value(self) -> f32 {
+        self.value
+    }
+}
+
+impl dimension::Dimension for Lovibond {
+    fn value(self) -> f32 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn conversions() {
+        let pale_ale = Srm::new(10.0).unwrap();
+        assert_approx_eq!(Ebc::from(pale_ale).value, 19.7, 0.001);
+        assert_approx_eq!(Lovibond::from(pale_ale).value, 7.944, 0.001);
+
+        let crystal = Ebc::new(120.0).unwrap();
+        let round_trip: Ebc = Srm::from(Lovibond::from(crystal)).into();
+        assert_approx_eq!(round_trip.value, 120.0, 0.001);
+
+        let pilsner = Lovibond::new(1.6).unwrap();
+        assert_approx_eq!(Srm::from(pilsner).value, 1.407, 0.001);
+        assert_approx_eq!(Ebc::from(pilsner).value, 2.772, 0.001);
+    }
+
+    #[test]
+    fn pale_lovibond_is_clamped() {
+        let colour = Srm::from(Lovibond::new(0.3).unwrap());
+        assert_eq!(colour.value, 0.0);
+    }
+
+    #[test]
+    fn invalid_values() {
+        assert!(Srm::new(-1.0).is_err());
+        assert!(Ebc::new(f32::NAN).is_err());
+        assert!(Lovibond::new(0.0).is_ok());
+    }
+}
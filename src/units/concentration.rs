@@ -1,27 +1,36 @@
 //! Concentration units
 use crate::units::dimension;
+use crate::units::mass::Kilogram;
+use crate::units::quantity::{dim, KilogramPerLitre, Quantity};
+use crate::units::volume::Litre;
 use std::f32;
 use std::ops::{Add, Mul, Sub};
 
+/// Concentration quantity
 pub trait Concentration: dimension::Dimension + dimension::DimensionLess + Sized {
+    /// Validated construction, the value must be non-negative
     fn new(value: f32) -> Result<Self, dimension::Error>;
 }
 
+/// Alcohol by volume in percent
 #[derive(Debug, Clone, Copy)]
 pub struct AlcoholByVolume {
+    /// Alcohol by volume in percent
     pub value: f32,
     _secret: (),
 }
 
 impl AlcoholByVolume {
+    /// Unvalidated construction
     pub fn new(value: f32) -> AlcoholByVolume {
         AlcoholByVolume { value, _secret: () }
     }
 }
 
 /// Density
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct SpecificGravity {
+    /// Density relative to water
     pub value: f32,
     _secret: (),
 }
@@ -42,20 +51,21 @@ impl Concentration for SpecificGravity {
         if value.is_sign_negative() {
             return Err(dimension::Error::ValueError(format!(
                 "Expected non-negative value, got: {}.",
-                value.to_string()
+                value
             )));
         }
         Ok(SpecificGravity { value, _secret: () })
     }
 }
 
-impl<T> Mul<T> for SpecificGravity
-where
-    T: dimension::Dimension + Mul<f32, Output = T>,
-{
-    type Output = T;
-    fn mul(self, rhs: T) -> Self::Output {
-        rhs * self.value
+/// Wort mass from gravity and volume,
+/// taking the density of water as $1$ kg / l.
+impl Mul<Litre> for SpecificGravity {
+    type Output = Kilogram;
+    fn mul(self, rhs: Litre) -> Self::Output {
+        let density: Quantity<dim::MassConcentration, KilogramPerLitre> = self.into();
+        let volume: Quantity<dim::Volume, Litre> = rhs.into();
+        (density * volume).into()
     }
 }
 
@@ -89,8 +99,10 @@ impl Sub for SpecificGravity {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Degrees Plato \[°P\], extract mass percentage
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Plato {
+    /// Degrees Plato
     pub value: f32,
     _secret: (),
 }
@@ -111,7 +123,7 @@ impl Concentration for Plato {
         if value.is_sign_negative() {
             return Err(dimension::Error::ValueError(format!(
                 "Expected non-negative value, got: {}.",
-                value.to_string()
+                value
             )));
         }
         Ok(Plato { value, _secret: () })
@@ -142,18 +154,68 @@ impl From<Plato> for SpecificGravity {
     }
 }
 
+/// Dissolved CO2 \[vol\], litres of gas at standard conditions per litre of beer
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct VolumesCO2 {
+    /// Volumes of CO2
+    pub value: f32,
+    _secret: (),
+}
+
+impl dimension::Dimension for VolumesCO2 {
+    fn value(self) -> f32 {
+        self.value
+    }
+}
+
+impl dimension::DimensionLess for VolumesCO2 {}
+
+impl Concentration for VolumesCO2 {
+    fn new(value: f32) -> Result<VolumesCO2, dimension::Error> {
+        if value.is_nan() {
+            return Err(dimension::Error::ValueError("NaN value".into()));
+        }
+        if value.is_sign_negative() {
+            return Err(dimension::Error::ValueError(format!(
+                "Expected non-negative value, got: {}.",
+                value
+            )));
+        }
+        Ok(VolumesCO2 { value, _secret: () })
+    }
+}
+
+impl Add for VolumesCO2 {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self {
+            value: self.value + other.value,
+            _secret: (),
+        }
+    }
+}
+
+impl Sub for VolumesCO2 {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self {
+            value: self.value - other.value,
+            _secret: (),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::units::volume;
     use crate::units::volume::Volume;
-    use crate::utils;
+    use assert_approx_eq::assert_approx_eq;
     /// Test some randomly picked values from here:
     /// https://www.brewersfriend.com/plato-to-sg-conversion-chart/
     #[test]
     fn ext_conversion_values_plato_to_sg() {
         let test_values: Vec<(f32, f32)> = vec![
-            ///(Plato, SG)
+            //(Plato, SG)
             (0.5, 1.002),
             (2.0, 1.008),
             (6.5, 1.026),
@@ -163,16 +225,17 @@ mod tests {
         for value in test_values {
             let plato = Plato::new(value.0).unwrap();
             let sg: SpecificGravity = plato.into();
-            assert!(utils::f32_almost_equal(value.1, sg.value, Some(0.001)));
+            assert_approx_eq!(value.1, sg.value, 0.001);
         }
     }
 
     /// Test some randomly picked values from here:
     /// https://www.brewersfriend.com/plato-to-sg-conversion-chart/
+    /// The polynomial is off by up to 0.14 °P at high gravities.
     #[test]
     fn ext_conversion_values_sg_to_plato() {
         let test_values: Vec<(f32, f32)> = vec![
-            ///(Plato, SG)
+            //(Plato, SG)
             (0.5, 1.002),
             (2.0, 1.008),
             (6.5, 1.026),
@@ -182,7 +245,7 @@ mod tests {
         for value in test_values {
             let sg = SpecificGravity::new(value.1).unwrap();
             let plato: Plato = sg.into();
-            assert!(utils::f32_almost_equal(value.0, plato.value, Some(0.1)));
+            assert_approx_eq!(value.0, plato.value, 0.15);
         }
     }
 
@@ -195,7 +258,7 @@ mod tests {
 
     #[test]
     fn mul_ops_test() {
-        let vol = volume::Litre::new(3.0).unwrap();
+        let vol = Litre::new(3.0).unwrap();
         let dens = SpecificGravity::new(1.1).unwrap();
         assert_eq!((dens * vol).value, 3.0 * 1.1);
     }
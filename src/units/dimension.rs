@@ -1,21 +1,30 @@
+//! Common traits and errors for dimensioned quantities
 use std::error as std_error;
 
+/// Quantity with a dimension
 // TODO: Crate only access
 pub trait Dimension {
+    /// Raw value in the quantity's unit
     fn value(self) -> f32;
 }
 
+/// Marker for dimensionless quantities
 pub trait DimensionLess {}
 
+/// Error for invalid quantities
 #[derive(Debug, Clone, PartialEq)]
 pub enum Error {
+    /// Invalid value, e.g. NaN or negative
     ValueError(String),
+    /// Text that could not be parsed as a quantity
+    ParseError(String),
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             Error::ValueError(description) => write!(f, "{}", description),
+            Error::ParseError(description) => write!(f, "{}", description),
         }
     }
 }
@@ -24,6 +33,7 @@ impl std_error::Error for Error {
     fn description(&self) -> &str {
         match *self {
             Error::ValueError(_) => "ValueError",
+            Error::ParseError(_) => "ParseError",
         }
     }
 
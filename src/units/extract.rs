@@ -0,0 +1,178 @@
+//! Extract bookkeeping
+//!
+//! The amount of extract in a wort is conserved when its volume changes,
+//! only the concentration changes.
+//! Expressing the extract in gravity points times volume gives a common basis for boil-off,
+//! dilution, top-up and kettle losses.
+//!
+//! - $\rho$ \[-\]: Wort gravity,
+//! - $V$ \[l\]: Wort volume,
+//! - $E$ \[pt l\]: Gravity points.
+use crate::units::concentration::{Concentration, SpecificGravity};
+use crate::units::dimension;
+use crate::units::volume::Litre;
+use std::f32;
+use std::ops::{Add, Mul, Sub};
+
+/// Gravity points per unit of specific gravity above water.
+const POINTS_PER_GRAVITY: f32 = 1000.0;
+
+/// Gravity points $E$ \[pt l\]
+///
+/// $$
+///     E = 1000 (\rho - 1) V
+/// $$
+#[derive(Debug, Clone, Copy)]
+pub struct GravityPoints {
+    /// Gravity points times litres
+    pub value: f32,
+    _secret: (),
+}
+
+impl GravityPoints {
+    /// Validated construction from gravity points times litres.
+    pub fn new(value: f32) -> Result<GravityPoints, dimension::Error> {
+        if value.is_nan() {
+            return Err(dimension::Error::ValueError("NaN value".into()));
+        }
+        if value.is_sign_negative() {
+            return Err(dimension::Error::ValueError(format!(
+                "Expected non-negative value, got: {}.",
+                value
+            )));
+        }
+        Ok(GravityPoints { value, _secret: () })
+    }
+
+    /// Extract in a volume of wort with a given gravity.
+    ///
+    /// Gravities below water ($\rho < 1$) contain no extract.
+    ///
+    /// ```
+    /// # use brew_calculator::units::concentration::{Concentration, SpecificGravity};
+    /// # use brew_calculator::units::extract::GravityPoints;
+    /// # use brew_calculator::units::volume::{Litre, Volume};
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let gravity = SpecificGravity::new(1.050).unwrap();
+    /// let volume = Litre::new(20.0).unwrap();
+    /// let points = GravityPoints::from_gravity(gravity, volume);
+    /// assert_approx_eq!(points.value, 1000.0, 0.1);
+    /// ```
+    pub fn from_gravity(gravity: SpecificGravity, volume: Litre) -> GravityPoints {
+        let points = (gravity.value - 1.0).max(0.0) * POINTS_PER_GRAVITY;
+        GravityPoints {
+            value: points * volume.value,
+            _secret: (),
+        }
+    }
+
+    /// Gravity when the extract is dissolved in `volume`.
+    ///
+    /// $$
+    ///     \rho = 1 + \frac{E}{1000 V}
+    /// $$
+    pub fn gravity(self, volume: Litre) -> Result<SpecificGravity, dimension::Error> {
+        if volume.value <= 0.0 {
+            return Err(dimension::Error::ValueError(format!(
+                "Expected positive volume, got: {}.",
+                volume.value
+            )));
+        }
+        SpecificGravity::new(1.0 + self.value / (POINTS_PER_GRAVITY * volume.value))
+    }
+}
+
+impl dimension::Dimension for GravityPoints {
+    fn value(self) -> f32 {
+        self.value
+    }
+}
+
+impl Mul<f32> for GravityPoints {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self {
+            value: self.value * rhs,
+            _secret: (),
+        }
+    }
+}
+
+impl Add for GravityPoints {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self {
+            value: self.value + other.value,
+            _secret: (),
+        }
+    }
+}
+
+/// Removing more extract than there is fails.
+impl Sub for GravityPoints {
+    type Output = Result<Self, dimension::Error>;
+    fn sub(self, other: Self) -> Self::Output {
+        GravityPoints::new(self.value - other.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::volume::Volume;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn boil_off_concentrates_wort() {
+        let pre_boil = GravityPoints::from_gravity(
+            SpecificGravity::new(1.040).unwrap(),
+            Litre::new(30.0).unwrap(),
+        );
+        let og = pre_boil.gravity(Litre::new(24.0).unwrap()).unwrap();
+        assert_approx_eq!(og.value, 1.050, 0.0001);
+    }
+
+    #[test]
+    fn blend_and_kettle_loss() {
+        let wort = GravityPoints::from_gravity(
+            SpecificGravity::new(1.060).unwrap(),
+            Litre::new(20.0).unwrap(),
+        );
+        let top_up = GravityPoints::from_gravity(
+            SpecificGravity::new(1.000).unwrap(),
+            Litre::new(4.0).unwrap(),
+        );
+        let blended = wort + top_up;
+        assert_approx_eq!(
+            blended.gravity(Litre::new(24.0).unwrap()).unwrap().value,
+            1.050,
+            0.0001
+        );
+
+        let loss = GravityPoints::from_gravity(
+            SpecificGravity::new(1.050).unwrap(),
+            Litre::new(2.0).unwrap(),
+        );
+        let remaining = (blended - loss).unwrap();
+        assert_approx_eq!(
+            remaining.gravity(Litre::new(22.0).unwrap()).unwrap().value,
+            1.050,
+            0.0001
+        );
+    }
+
+    #[test]
+    fn gravity_in_empty_volume_fails() {
+        let points = GravityPoints::new(100.0).unwrap();
+        assert!(points.gravity(Litre::new(0.0).unwrap()).is_err());
+        assert!(GravityPoints::new(-1.0).is_err());
+    }
+
+    #[test]
+    fn negative_extract_fails() {
+        let volume = Litre::new(1.0).unwrap();
+        let weak = GravityPoints::from_gravity(SpecificGravity::new(1.010).unwrap(), volume);
+        let strong = GravityPoints::from_gravity(SpecificGravity::new(1.050).unwrap(), volume);
+        assert!((weak - strong).is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Formatting of quantities
+//!
+//! The [`Display`](fmt::Display) implementations write the value with a default precision
+//! for the unit, followed by the unit symbol.
+//! An explicit precision in the format string takes precedence:
+//!
+//! ```
+//! # use brew_calculator::units::concentration::{Concentration, Plato, SpecificGravity};
+//! let og = SpecificGravity::new(1.0512).unwrap();
+//! assert_eq!(og.to_string(), "1.051");
+//! let og = Plato::new(12.64).unwrap();
+//! assert_eq!(og.to_string(), "12.6 °P");
+//! assert_eq!(format!("{:.2}", og), "12.64 °P");
+//! ```
+//!
+//! The symbols are those used by [`serialize`](super::serialize), so the text can be
+//! deserialized again.
+//!
+//! [`UnitSystem`] converts to the preferred unit system before formatting.
+use crate::units::colour::{Ebc, Lovibond, Srm};
+use crate::units::concentration::{Plato, SpecificGravity, VolumesCO2};
+use crate::units::mass::{Gram, Kilogram, Ounce, Pound};
+use crate::units::pressure::{Bar, Psi};
+use crate::units::temperature::{Celsius, Fahrenheit, Kelvin, TemperatureDifference};
+use crate::units::volume::{
+    Decilitre, Hectolitre, ImperialBarrel, ImperialFluidOunce, ImperialGallon, Litre, Millilitre,
+    UsBarrel, UsFluidOunce, UsGallon,
+};
+use crate::units::Ibu;
+use std::fmt;
+
+/// [`Display`](fmt::Display) with a unit symbol and default precision.
+macro_rules! display {
+    ($($name:ident: $symbol:expr, $precision:expr;)*) => {
+        $(
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    let precision = f.precision().unwrap_or($precision);
+                    write!(f, "{:.*} {}", precision, self.value, $symbol)
+                }
+            }
+        )*
+    };
+}
+
+display!(
+    Kilogram: "kg", 2;
+    Gram: "g", 1;
+    Pound: "lb", 2;
+    Ounce: "oz", 2;
+    Litre: "L", 1;
+    Millilitre: "ml", 0;
+    Decilitre: "dl", 1;
+    Hectolitre: "hl", 2;
+    UsFluidOunce: "fl oz", 1;
+    ImperialFluidOunce: "imp fl oz", 1;
+    UsGallon: "gal", 2;
+    ImperialGallon: "imp gal", 2;
+    UsBarrel: "bbl", 2;
+    ImperialBarrel: "imp bbl", 2;
+    Celsius: "°C", 1;
+    Fahrenheit: "°F", 1;
+    Kelvin: "K", 1;
+    TemperatureDifference: "K", 1;
+    Plato: "°P", 1;
+    VolumesCO2: "vol", 2;
+    Bar: "bar", 2;
+    Psi: "psi", 1;
+    Srm: "SRM", 1;
+    Ebc: "EBC", 0;
+    Lovibond: "°L", 1;
+);
+
+impl fmt::Display for SpecificGravity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        write!(f, "{:.*}", precision, self.value)
+    }
+}
+
+/// Bitterness rounded to whole IBU
+///
+/// ```
+/// # use brew_calculator::units::format::format_ibu;
+/// assert_eq!(format_ibu(34.6), "35 IBU");
+/// ```
+pub fn format_ibu(ibu: Ibu) -> String {
+    format!("{:.0} IBU", ibu)
+}
+
+/// Preferred unit system for presenting quantities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnitSystem {
+    /// Kilograms, litres, degrees Celsius and bar
+    #[default]
+    Metric,
+    /// US customary pounds, gallons, degrees Fahrenheit and psi
+    Us,
+}
+
+impl UnitSystem {
+    /// Mass in kg or lb, or g or oz for masses below one kg or lb
+    ///
+    /// ```
+    /// # use brew_calculator::units::format::UnitSystem;
+    /// # use brew_calculator::units::mass::{Kilogram, Mass};
+    /// let hops = Kilogram::new(0.0283).unwrap();
+    /// assert_eq!(UnitSystem::Metric.mass(hops), "28.3 g");
+    /// assert_eq!(UnitSystem::Us.mass(hops), "1.00 oz");
+    /// ```
+    pub fn mass(self, mass: Kilogram) -> String {
+        match self {
+            UnitSystem::Metric if mass.value < 1.0 => Gram::from(mass).to_string(),
+            UnitSystem::Metric => mass.to_string(),
+            UnitSystem::Us => {
+                let pounds = Pound::from(mass);
+                if pounds.value < 1.0 {
+                    Ounce::from(mass).to_string()
+                } else {
+                    pounds.to_string()
+                }
+            }
+        }
+    }
+
+    /// Volume in L, or gal or fl oz for volumes below one gallon
+    pub fn volume(self, volume: Litre) -> String {
+        match self {
+            UnitSystem::Metric => volume.to_string(),
+            UnitSystem::Us => {
+                let gallons = UsGallon::from(volume);
+                if gallons.value < 1.0 {
+                    UsFluidOunce::from(volume).to_string()
+                } else {
+                    gallons.to_string()
+                }
+            }
+        }
+    }
+
+    /// Temperature in °C or °F
+    pub fn temperature(self, temperature: Celsius) -> String {
+        match self {
+            UnitSystem::Metric => temperature.to_string(),
+            UnitSystem::Us => Fahrenheit::from(temperature).to_string(),
+        }
+    }
+
+    /// Pressure in bar or psi
+    pub fn pressure(self, pressure: Bar) -> String {
+        match self {
+            UnitSystem::Metric => pressure.to_string(),
+            UnitSystem::Us => Psi::from(pressure).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::colour::Colour;
+    use crate::units::concentration::Concentration;
+    use crate::units::mass::Mass;
+    use crate::units::pressure::Pressure;
+    use crate::units::temperature::Temperature;
+    use crate::units::volume::Volume;
+
+    #[test]
+    fn display_precision() {
+        let gravity = SpecificGravity::new(1.0455).unwrap();
+        assert_eq!(gravity.to_string(), "1.046");
+        assert_eq!(format!("{:.4}", gravity), "1.0455");
+        assert_eq!(Litre::new(23.04).unwrap().to_string(), "23.0 L");
+        let text = serde_json::to_string(&Litre::new(23.0).unwrap().to_string()).unwrap();
+        assert_eq!(serde_json::from_str::<Litre>(&text).unwrap().value, 23.0);
+        assert_eq!(Celsius::new(-1.56).unwrap().to_string(), "-1.6 °C");
+        assert_eq!(format_ibu(12.4), "12 IBU");
+        assert_eq!(VolumesCO2::new(2.4).unwrap().to_string(), "2.40 vol");
+        assert_eq!(Srm::new(7.25).unwrap().to_string(), "7.2 SRM");
+        assert_eq!(Ebc::new(14.3).unwrap().to_string(), "14 EBC");
+    }
+
+    #[test]
+    fn unit_system_conversion() {
+        let batch = Litre::new(18.927).unwrap();
+        assert_eq!(UnitSystem::Metric.volume(batch), "18.9 L");
+        assert_eq!(UnitSystem::Us.volume(batch), "5.00 gal");
+        assert_eq!(
+            UnitSystem::Us.volume(Litre::new(0.355).unwrap()),
+            "12.0 fl oz"
+        );
+
+        let mash = Celsius::new(66.7).unwrap();
+        assert_eq!(UnitSystem::Us.temperature(mash), "152.1 °F");
+        assert_eq!(UnitSystem::default().temperature(mash), "66.7 °C");
+
+        let malt = Kilogram::new(4.536).unwrap();
+        assert_eq!(UnitSystem::Metric.mass(malt), "4.54 kg");
+        assert_eq!(UnitSystem::Us.mass(malt), "10.00 lb");
+
+        let keg = Bar::new(0.83).unwrap();
+        assert_eq!(UnitSystem::Metric.pressure(keg), "0.83 bar");
+        assert_eq!(UnitSystem::Us.pressure(keg), "12.0 psi");
+    }
+}
@@ -0,0 +1,69 @@
+//! Mass units
+//!
+//! Metric and imperial (avoirdupois) mass units, with conversions between all of them.
+use crate::units::dimension;
+use crate::units::quantity::dim;
+
+/// Mass quantity
+pub trait Mass: dimension::Dimension + Sized {
+    /// Validated construction, the value must be non-negative
+    fn new(value: f32) -> Result<Self, dimension::Error>;
+}
+
+/// Kilograms per avoirdupois pound, exactly $0.45359237$
+const KILOGRAMS_PER_POUND: f64 = 0.453_592_37;
+/// Kilograms per pound, for compound units such as points per pound per gallon
+pub(crate) const KILOGRAMS_PER_POUND_F32: f32 = KILOGRAMS_PER_POUND as f32;
+
+linear_unit!(
+    /// Kilogram \[kg\]
+    Kilogram: Mass, 1.0
+);
+
+linear_unit!(
+    /// Gram \[g\]
+    Gram: Mass, 1.0e-3
+);
+
+linear_unit!(
+    /// Avoirdupois pound \[lb\]
+    Pound: Mass, KILOGRAMS_PER_POUND
+);
+
+linear_unit!(
+    /// Avoirdupois ounce \[oz\], $1/16$ lb
+    Ounce: Mass, KILOGRAMS_PER_POUND / 16.0
+);
+
+unit_conversions!(Kilogram, Gram, Pound, Ounce);
+quantity_units!(dim::Mass: Kilogram, Gram, Pound, Ounce);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn conversions() {
+        let mass = Pound::new(1.0).unwrap();
+        assert_approx_eq!(Kilogram::from(mass).value, 0.453_592_37, 1e-7);
+        assert_approx_eq!(Ounce::from(mass).value, 16.0, 1e-5);
+        assert_approx_eq!(Gram::from(Kilogram::new(0.007).unwrap()).value, 7.0, 1e-5);
+        assert_approx_eq!(Ounce::from(Gram::new(28.349_523).unwrap()).value, 1.0, 1e-6);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mass = Gram::new(12.3).unwrap();
+        let round_trip: Gram = Ounce::from(Pound::from(Kilogram::from(mass))).into();
+        assert_eq!(round_trip.value, mass.value);
+    }
+
+    #[test]
+    fn validation() {
+        assert!(Kilogram::new(-1.0).is_err());
+        assert!(Gram::new(f32::NAN).is_err());
+        let total = Kilogram::new(1.5).unwrap() + Kilogram::new(2.0).unwrap() * 2.0;
+        assert_approx_eq!(total.value, 5.5, 1e-6);
+    }
+}
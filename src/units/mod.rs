@@ -1,6 +1,174 @@
-// mod concentration;
-// mod dimension;
-// mod volume;
+//! Units of measurement
+//!
+//! Quantities are newtypes with a public `value` and a private `_secret` field,
+//! so that they can only be constructed through their validating `new`, also from within
+//! the crate, which `#[non_exhaustive]` would not prevent.
+
+#![allow(clippy::manual_non_exhaustive)]
+
+/// Validated newtype for a unit with a linear scale, e.g. mass or volume.
+///
+/// The unit implements `$dimension`, a trait with a validated `new` constructor,
+/// and knows its size in the base unit of the dimension,
+/// which is what the conversions from `unit_conversions` are based on.
+macro_rules! linear_unit {
+    ($(#[$meta:meta])* $name:ident: $dimension:ident, $base_factor:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+        pub struct $name {
+            /// Value in the unit
+            pub value: f32,
+            _secret: (),
+        }
+
+        impl $name {
+            /// Size of the unit in the base unit of the dimension
+            const BASE_FACTOR: f64 = $base_factor;
+        }
+
+        impl $crate::units::dimension::Dimension for $name {
+            fn value(self) -> f32 {
+                self.value
+            }
+        }
+
+        impl $dimension for $name {
+            fn new(value: f32) -> Result<$name, $crate::units::dimension::Error> {
+                if value.is_nan() {
+                    return Err($crate::units::dimension::Error::ValueError(
+                        "NaN value".into(),
+                    ));
+                }
+                if value.is_sign_negative() {
+                    return Err($crate::units::dimension::Error::ValueError(format!(
+                        "Expected non-negative value, got: {}.",
+                        value
+                    )));
+                }
+                Ok($name { value, _secret: () })
+            }
+        }
+
+        impl std::ops::Add for $name {
+            type Output = Self;
+            fn add(self, rhs: $name) -> Self::Output {
+                $name {
+                    value: self.value + rhs.value,
+                    _secret: (),
+                }
+            }
+        }
+
+        /// Removing more than there is fails.
+        impl std::ops::Sub for $name {
+            type Output = Result<Self, $crate::units::dimension::Error>;
+            fn sub(self, rhs: $name) -> Self::Output {
+                <$name as $dimension>::new(self.value - rhs.value)
+            }
+        }
+
+        impl std::ops::Mul<f32> for $name {
+            type Output = Self;
+            fn mul(self, rhs: f32) -> Self::Output {
+                $name {
+                    value: self.value * rhs,
+                    _secret: (),
+                }
+            }
+        }
+
+        impl std::ops::Div<f32> for $name {
+            type Output = Self;
+            fn div(self, rhs: f32) -> Self::Output {
+                $name {
+                    value: self.value / rhs,
+                    _secret: (),
+                }
+            }
+        }
+
+        /// Ratio of two quantities in the same unit
+        impl std::ops::Div for $name {
+            type Output = f32;
+            fn div(self, rhs: $name) -> Self::Output {
+                self.value / rhs.value
+            }
+        }
+    };
+}
+
+/// Conversions between all pairs of the given units of a dimension.
+///
+/// The conversion factors are computed in double precision from the exact unit definitions,
+/// so a round trip only loses the precision of the `f32` value itself.
+macro_rules! unit_conversions {
+    () => {};
+    ($first:ident $(, $rest:ident)*) => {
+        $(
+            impl From<$first> for $rest {
+                fn from(quantity: $first) -> $rest {
+                    $rest {
+                        value: (f64::from(quantity.value) * $first::BASE_FACTOR
+                            / $rest::BASE_FACTOR) as f32,
+                        _secret: (),
+                    }
+                }
+            }
+
+            impl From<$rest> for $first {
+                fn from(quantity: $rest) -> $first {
+                    $first {
+                        value: (f64::from(quantity.value) * $rest::BASE_FACTOR
+                            / $first::BASE_FACTOR) as f32,
+                        _secret: (),
+                    }
+                }
+            }
+        )*
+        unit_conversions!($($rest),*);
+    };
+}
+
+/// Makes the given unit newtypes markers for quantities of `$dimension`,
+/// with conversions between the newtype and the corresponding quantity.
+macro_rules! quantity_units {
+    ($dimension:ty: $($name:ident),*) => {
+        $(
+            impl $crate::units::quantity::Unit for $name {
+                type Dimension = $dimension;
+                const BASE_FACTOR: f64 = $name::BASE_FACTOR;
+            }
+
+            impl From<$name> for $crate::units::quantity::Quantity<$dimension, $name> {
+                fn from(quantity: $name) -> Self {
+                    $crate::units::quantity::Quantity::from_value(quantity.value)
+                }
+            }
+
+            impl From<$crate::units::quantity::Quantity<$dimension, $name>> for $name {
+                fn from(quantity: $crate::units::quantity::Quantity<$dimension, $name>) -> Self {
+                    $name {
+                        value: quantity.value,
+                        _secret: (),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+pub mod colour;
+pub mod concentration;
+pub mod dimension;
+pub mod extract;
+pub mod format;
+pub mod mass;
+pub mod parse;
+pub mod pressure;
+pub mod quantity;
+pub mod serialize;
+pub mod temperature;
+pub mod volume;
 
 /// Value as in decimal * 100
 pub type Percent = f32;
@@ -12,18 +180,8 @@ pub type PH = f32;
 pub type SpecificGravity = f32;
 /// International bitternes units
 pub type Ibu = f32;
-/// Standard reference method
-///
-/// [Reference](https://beermaverick.com/understanding-srm-and-lovibond-beer-color-calculations/)
-pub type SRMColor = f32;
-/// Carbonation
-///
-/// TODO: properly documents
-pub type VolumesCO2 = f32;
 /// Alcohol by volume in percent
 pub type Abv = Percent;
-/// Temperature
-pub type Celsius = f32;
 /// Volume
 pub type Liters = f32;
 /// Mass
@@ -0,0 +1,382 @@
+//! Parsing quantities from text
+//!
+//! Quantities are written as a number followed by an optional unit, e.g. `"5.5 gal"`,
+//! `"12 °P"` or `"1.050"`.
+//! Both `.` and `,` are accepted as decimal separator, the latter being the Swedish
+//! convention, but thousands separators are not.
+//! Unit symbols and names are matched case insensitively, ignoring spaces, periods and
+//! degree signs, so `"°C"`, `"C"` and `"celsius"` are all degrees Celsius.
+//! The exception is `"°L"`, degrees Lovibond, which needs the degree sign to not be litres.
+//!
+//! [`parse_quantity`] finds the dimension from the unit, while the [`FromStr`]
+//! implementations of the unit types convert any unit of the right dimension and take a
+//! number without unit to be in the type's own unit.
+//!
+//! ```
+//! # use brew_calculator::units::volume::Litre;
+//! # use assert_approx_eq::assert_approx_eq;
+//! let batch: Litre = "5,5 gal".parse().unwrap();
+//! assert_approx_eq!(batch.value, 20.82, 0.01);
+//! ```
+use crate::units::colour::{Colour, Ebc, Lovibond, Srm};
+use crate::units::concentration::{Concentration, Plato, SpecificGravity, VolumesCO2};
+use crate::units::dimension;
+use crate::units::mass::{Gram, Kilogram, Mass, Ounce, Pound};
+use crate::units::pressure::{Bar, Pressure, Psi};
+use crate::units::temperature::{Celsius, Fahrenheit, Kelvin, Temperature};
+use crate::units::volume::{
+    Decilitre, Hectolitre, ImperialBarrel, ImperialFluidOunce, ImperialGallon, Litre, Millilitre,
+    UsBarrel, UsFluidOunce, UsGallon, Volume,
+};
+use std::str::FromStr;
+
+/// Quantity parsed from text, tagged with its dimension
+///
+/// Values are converted to the default unit of the dimension.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParsedQuantity {
+    /// Mass, e.g. `"25 g"` or `"1 lb"`
+    Mass(Kilogram),
+    /// Volume, e.g. `"20 l"` or `"5.5 gal"`
+    Volume(Litre),
+    /// Temperature, e.g. `"66 °C"` or `"152 F"`
+    Temperature(Celsius),
+    /// Specific gravity, e.g. `"1.050"` or `"1.050 SG"`
+    SpecificGravity(SpecificGravity),
+    /// Degrees Plato, e.g. `"12 °P"`
+    Plato(Plato),
+    /// Dissolved CO2, e.g. `"2.4 vol"`
+    Carbonation(VolumesCO2),
+    /// Gauge pressure, e.g. `"0.8 bar"` or `"12 psi"`
+    Pressure(Bar),
+    /// Beer colour, e.g. `"10 SRM"` or `"20 EBC"`
+    Colour(Srm),
+    /// Grain colour, e.g. `"60 °L"`
+    Lovibond(Lovibond),
+}
+
+/// Parse a quantity with a unit
+///
+/// A number without unit is a specific gravity.
+///
+/// ```
+/// # use brew_calculator::units::parse::{parse_quantity, ParsedQuantity};
+/// # use assert_approx_eq::assert_approx_eq;
+/// match parse_quantity("12 °P").unwrap() {
+///     ParsedQuantity::Plato(plato) => assert_approx_eq!(plato.value, 12.0),
+///     other => panic!("Expected a Plato value, got {:?}", other),
+/// }
+/// ```
+pub fn parse_quantity(input: &str) -> Result<ParsedQuantity, dimension::Error> {
+    let (value, unit) = split_value(input)?;
+    parse_unit(value, unit)
+}
+
+/// Splits the input into a number and the remaining unit.
+fn split_value(input: &str) -> Result<(f32, &str), dimension::Error> {
+    let input = input.trim();
+    let unit_start = input
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ',' || c == '-' || c == '+'))
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(unit_start);
+    if number.contains('.') && number.contains(',') {
+        return Err(dimension::Error::ParseError(format!(
+            "Ambiguous decimal separator in: {}.",
+            input
+        )));
+    }
+    let value = number
+        .replace(',', ".")
+        .parse::<f32>()
+        .map_err(|_| dimension::Error::ParseError(format!("Expected a number, got: {}.", input)))?;
+    Ok((value, unit.trim()))
+}
+
+fn parse_unit(value: f32, unit: &str) -> Result<ParsedQuantity, dimension::Error> {
+    let normalised: String = unit
+        .chars()
+        .filter(|c| !(c.is_whitespace() || *c == '.' || *c == '°' || *c == 'º'))
+        .collect::<String>()
+        .to_lowercase();
+    if normalised == "l" && unit.contains(['°', 'º']) {
+        return Ok(ParsedQuantity::Lovibond(Lovibond::new(value)?));
+    }
+    let quantity = match normalised.as_str() {
+        "kg" | "kilo" | "kilos" | "kilogram" | "kilograms" => {
+            ParsedQuantity::Mass(Kilogram::new(value)?)
+        }
+        "g" | "gram" | "grams" => ParsedQuantity::Mass(Gram::new(value)?.into()),
+        "lb" | "lbs" | "pound" | "pounds" => ParsedQuantity::Mass(Pound::new(value)?.into()),
+        "oz" | "ounce" | "ounces" => ParsedQuantity::Mass(Ounce::new(value)?.into()),
+        "l" | "liter" | "liters" | "litre" | "litres" => ParsedQuantity::Volume(Litre::new(value)?),
+        "ml" | "milliliter" | "milliliters" | "millilitre" | "millilitres" => {
+            ParsedQuantity::Volume(Millilitre::new(value)?.into())
+        }
+        "dl" | "deciliter" | "deciliters" | "decilitre" | "decilitres" => {
+            ParsedQuantity::Volume(Decilitre::new(value)?.into())
+        }
+        "hl" | "hectoliter" | "hectoliters" | "hectolitre" | "hectolitres" => {
+            ParsedQuantity::Volume(Hectolitre::new(value)?.into())
+        }
+        "floz" | "usfloz" | "fluidounce" | "fluidounces" | "ozfl" => {
+            ParsedQuantity::Volume(UsFluidOunce::new(value)?.into())
+        }
+        "impfloz" | "ukfloz" => ParsedQuantity::Volume(ImperialFluidOunce::new(value)?.into()),
+        "gal" | "usgal" | "gallon" | "gallons" => {
+            ParsedQuantity::Volume(UsGallon::new(value)?.into())
+        }
+        "impgal" | "ukgal" => ParsedQuantity::Volume(ImperialGallon::new(value)?.into()),
+        "bbl" | "usbbl" | "barrel" | "barrels" => {
+            ParsedQuantity::Volume(UsBarrel::new(value)?.into())
+        }
+        "impbbl" | "ukbbl" => ParsedQuantity::Volume(ImperialBarrel::new(value)?.into()),
+        "c" | "degc" | "celsius" => ParsedQuantity::Temperature(Celsius::new(value)?),
+        "f" | "degf" | "fahrenheit" => ParsedQuantity::Temperature(Fahrenheit::new(value)?.into()),
+        "k" | "kelvin" => ParsedQuantity::Temperature(Kelvin::new(value)?.into()),
+        "" | "sg" => ParsedQuantity::SpecificGravity(SpecificGravity::new(value)?),
+        "p" | "plato" => ParsedQuantity::Plato(Plato::new(value)?),
+        "vol" | "vols" | "volumes" | "volco2" | "volumesco2" => {
+            ParsedQuantity::Carbonation(VolumesCO2::new(value)?)
+        }
+        "bar" => ParsedQuantity::Pressure(Bar::new(value)?),
+        "psi" | "psig" => ParsedQuantity::Pressure(Psi::new(value)?.into()),
+        "srm" => ParsedQuantity::Colour(Srm::new(value)?),
+        "ebc" => ParsedQuantity::Colour(Ebc::new(value)?.into()),
+        "degl" | "lovibond" => ParsedQuantity::Lovibond(Lovibond::new(value)?),
+        _ => {
+            return Err(dimension::Error::ParseError(format!(
+                "Unknown unit: {}.",
+                unit
+            )))
+        }
+    };
+    Ok(quantity)
+}
+
+/// Construction from a value and a unit, which may be any unit of the same dimension.
+///
+/// An empty unit is the type's own unit.
+pub(crate) trait FromValueUnit: Sized {
+    fn from_value_unit(value: f32, unit: &str) -> Result<Self, dimension::Error>;
+}
+
+/// [`FromValueUnit`] for unit types of a dimension.
+macro_rules! from_value_unit {
+    ($variant:ident: $($name:ident),*) => {
+        $(
+            impl FromValueUnit for $name {
+                fn from_value_unit(value: f32, unit: &str) -> Result<$name, dimension::Error> {
+                    if unit.is_empty() {
+                        return $name::new(value);
+                    }
+                    match parse_unit(value, unit)? {
+                        ParsedQuantity::$variant(quantity) => Ok(quantity.into()),
+                        other => Err(dimension::Error::ParseError(format!(
+                            "Expected {}, got: {:?}.",
+                            stringify!($name),
+                            other
+                        ))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_value_unit!(Mass: Kilogram, Gram, Pound, Ounce);
+from_value_unit!(
+    Volume: Litre,
+    Millilitre,
+    Decilitre,
+    Hectolitre,
+    UsFluidOunce,
+    ImperialFluidOunce,
+    UsGallon,
+    ImperialGallon,
+    UsBarrel,
+    ImperialBarrel
+);
+from_value_unit!(Temperature: Celsius, Fahrenheit, Kelvin);
+from_value_unit!(Carbonation: VolumesCO2);
+from_value_unit!(Pressure: Bar, Psi);
+
+impl FromValueUnit for SpecificGravity {
+    fn from_value_unit(value: f32, unit: &str) -> Result<SpecificGravity, dimension::Error> {
+        match parse_unit(value, unit)? {
+            ParsedQuantity::SpecificGravity(gravity) => Ok(gravity),
+            ParsedQuantity::Plato(plato) => Ok(plato.into()),
+            other => Err(dimension::Error::ParseError(format!(
+                "Expected SpecificGravity, got: {:?}.",
+                other
+            ))),
+        }
+    }
+}
+
+impl FromValueUnit for Plato {
+    fn from_value_unit(value: f32, unit: &str) -> Result<Plato, dimension::Error> {
+        if unit.is_empty() {
+            return Plato::new(value);
+        }
+        match parse_unit(value, unit)? {
+            ParsedQuantity::Plato(plato) => Ok(plato),
+            ParsedQuantity::SpecificGravity(gravity) => Ok(gravity.into()),
+            other => Err(dimension::Error::ParseError(format!(
+                "Expected Plato, got: {:?}.",
+                other
+            ))),
+        }
+    }
+}
+
+/// Colour in any colour unit, kept apart from [`ParsedQuantity::Colour`] so that
+/// Lovibond values are not rounded through SRM.
+fn colour_from_value_unit<C>(value: f32, unit: &str, name: &str) -> Result<C, dimension::Error>
+where
+    C: Colour + From<Srm> + From<Lovibond>,
+{
+    if unit.is_empty() {
+        return C::new(value);
+    }
+    match parse_unit(value, unit)? {
+        ParsedQuantity::Colour(colour) => Ok(colour.into()),
+        ParsedQuantity::Lovibond(colour) => Ok(colour.into()),
+        other => Err(dimension::Error::ParseError(format!(
+            "Expected {}, got: {:?}.",
+            name, other
+        ))),
+    }
+}
+
+impl FromValueUnit for Srm {
+    fn from_value_unit(value: f32, unit: &str) -> Result<Srm, dimension::Error> {
+        colour_from_value_unit(value, unit, "Srm")
+    }
+}
+
+impl FromValueUnit for Ebc {
+    fn from_value_unit(value: f32, unit: &str) -> Result<Ebc, dimension::Error> {
+        colour_from_value_unit(value, unit, "Ebc")
+    }
+}
+
+impl FromValueUnit for Lovibond {
+    fn from_value_unit(value: f32, unit: &str) -> Result<Lovibond, dimension::Error> {
+        colour_from_value_unit(value, unit, "Lovibond")
+    }
+}
+
+/// [`FromStr`] through [`FromValueUnit`].
+macro_rules! from_str {
+    ($($name:ident),*) => {
+        $(
+            impl FromStr for $name {
+                type Err = dimension::Error;
+                fn from_str(input: &str) -> Result<$name, Self::Err> {
+                    let (value, unit) = split_value(input)?;
+                    $name::from_value_unit(value, unit)
+                }
+            }
+        )*
+    };
+}
+
+from_str!(
+    Kilogram,
+    Gram,
+    Pound,
+    Ounce,
+    Litre,
+    Millilitre,
+    Decilitre,
+    Hectolitre,
+    UsFluidOunce,
+    ImperialFluidOunce,
+    UsGallon,
+    ImperialGallon,
+    UsBarrel,
+    ImperialBarrel,
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    SpecificGravity,
+    Plato,
+    VolumesCO2,
+    Bar,
+    Psi,
+    Srm,
+    Ebc,
+    Lovibond
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn parse_tagged_quantities() {
+        match parse_quantity("5.5 gal").unwrap() {
+            ParsedQuantity::Volume(volume) => assert_approx_eq!(volume.value, 20.82, 0.01),
+            other => panic!("Unexpected {:?}", other),
+        }
+        match parse_quantity("1,050").unwrap() {
+            ParsedQuantity::SpecificGravity(gravity) => assert_approx_eq!(gravity.value, 1.05),
+            other => panic!("Unexpected {:?}", other),
+        }
+        match parse_quantity(" 152°F ").unwrap() {
+            ParsedQuantity::Temperature(temperature) => {
+                assert_approx_eq!(temperature.value, 66.67, 0.01)
+            }
+            other => panic!("Unexpected {:?}", other),
+        }
+        match parse_quantity("28 g").unwrap() {
+            ParsedQuantity::Mass(mass) => assert_approx_eq!(mass.value, 0.028),
+            other => panic!("Unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_unit_types() {
+        let hops: Gram = "1 oz".parse().unwrap();
+        assert_approx_eq!(hops.value, 28.35, 0.01);
+        let hops: Gram = "25".parse().unwrap();
+        assert_approx_eq!(hops.value, 25.0);
+        let volume: Millilitre = "12 fl. oz.".parse().unwrap();
+        assert_approx_eq!(volume.value, 354.88, 0.01);
+        let mash: Celsius = "-2,5 C".parse().unwrap();
+        assert_approx_eq!(mash.value, -2.5);
+        let og: Plato = "1.048 SG".parse().unwrap();
+        assert_approx_eq!(og.value, 11.9, 0.1);
+        let carbonation: VolumesCO2 = "2,4 vol CO2".parse().unwrap();
+        assert_approx_eq!(carbonation.value, 2.4);
+        let pressure: Bar = "12 psi".parse().unwrap();
+        assert_approx_eq!(pressure.value, 0.827, 0.001);
+        let crystal: Ebc = "60 °L".parse().unwrap();
+        assert_approx_eq!(crystal.value, 158.7, 0.1);
+        let pilsner: Lovibond = "3.5 EBC".parse().unwrap();
+        assert_approx_eq!(pilsner.value, 1.873, 0.001);
+        assert!("2 l".parse::<Lovibond>().is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(
+            parse_quantity("5 parsecs"),
+            Err(dimension::Error::ParseError(_))
+        ));
+        assert!(matches!(
+            parse_quantity("1.000,5 l"),
+            Err(dimension::Error::ParseError(_))
+        ));
+        assert!(matches!(
+            parse_quantity("gal"),
+            Err(dimension::Error::ParseError(_))
+        ));
+        assert!(matches!(
+            parse_quantity("-5 l"),
+            Err(dimension::Error::ValueError(_))
+        ));
+        assert!("20 kg".parse::<Litre>().is_err());
+    }
+}
@@ -0,0 +1,36 @@
+//! Pressure units
+//!
+//! Gauge pressures, i.e. above atmospheric pressure, as read on a regulator.
+use crate::units::dimension;
+
+/// Pressure quantity
+pub trait Pressure: dimension::Dimension + Sized {
+    /// Validated construction, the value must be non-negative
+    fn new(value: f32) -> Result<Self, dimension::Error>;
+}
+
+linear_unit!(
+    /// Bar \[bar\], exactly $10^5$ Pa
+    Bar: Pressure, 1.0
+);
+
+linear_unit!(
+    /// Pound-force per square inch \[psi\], $6894.757$ Pa
+    Psi: Pressure, 0.068_947_572_931_683_6
+);
+
+unit_conversions!(Bar, Psi);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn conversions() {
+        let pressure = Bar::new(1.0).unwrap();
+        assert_approx_eq!(Psi::from(pressure).value, 14.503_77, 1e-4);
+        assert_approx_eq!(Bar::from(Psi::new(12.0).unwrap()).value, 0.827_37, 1e-5);
+        assert!(Psi::new(-1.0).is_err());
+    }
+}
@@ -0,0 +1,413 @@
+//! Compile time dimensional analysis
+//!
+//! A [`Quantity`] is a value tagged with a physical dimension and a unit.
+//! Both are zero-sized type parameters, so a quantity has the same size and runtime cost as
+//! an `f32`, while mixing up dimensions fails to compile:
+//!
+//! ```compile_fail
+//! # use brew_calculator::units::quantity::{dim, Quantity};
+//! # use brew_calculator::units::mass::Kilogram;
+//! # use brew_calculator::units::volume::Litre;
+//! let malt = Quantity::<dim::Mass, Kilogram>::new(5.0).unwrap();
+//! let water = Quantity::<dim::Volume, Litre>::new(20.0).unwrap();
+//! let nonsense = malt + water;
+//! ```
+//!
+//! Multiplying and dividing quantities gives the derived dimension,
+//! expressed in its base unit:
+//!
+//! ```
+//! # use brew_calculator::units::quantity::{dim, KilogramPerLitre, Quantity};
+//! # use brew_calculator::units::mass::Gram;
+//! # use brew_calculator::units::volume::Litre;
+//! # use assert_approx_eq::assert_approx_eq;
+//! let hops = Quantity::<dim::Mass, Gram>::new(50.0).unwrap();
+//! let wort = Quantity::<dim::Volume, Litre>::new(25.0).unwrap();
+//! let concentration: Quantity<dim::MassConcentration, KilogramPerLitre> = hops * wort.recip();
+//! assert_approx_eq!(concentration.value, 0.002, 1e-7);
+//! ```
+//!
+//! The unit newtypes, e.g. [`Kilogram`](crate::units::mass::Kilogram) and
+//! [`Litre`](crate::units::volume::Litre), double as unit markers and convert to
+//! and from the corresponding quantity.
+use crate::units::concentration::SpecificGravity;
+use crate::units::dimension;
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// Physical dimensions
+///
+/// The dimensions are zero-sized markers. Their products and quotients are defined by
+/// [`DimMul`] and [`DimDiv`].
+pub mod dim {
+    use super::{DimDiv, DimMul, Unit};
+
+    /// Physical dimension
+    pub trait PhysicalDimension {
+        /// The unit quantities of this dimension are expressed in after multiplication or
+        /// division
+        type BaseUnit: Unit<Dimension = Self>;
+    }
+
+    /// Mass \[kg\]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Mass;
+
+    /// Volume \[l\]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Volume;
+
+    /// Inverse volume, i.e. per volume \[1 / l\]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct InverseVolume;
+
+    /// Mass concentration or density, mass per volume \[kg / l\]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct MassConcentration;
+
+    /// Dimensionless \[-\]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Dimensionless;
+
+    impl PhysicalDimension for Mass {
+        type BaseUnit = crate::units::mass::Kilogram;
+    }
+
+    impl PhysicalDimension for Volume {
+        type BaseUnit = crate::units::volume::Litre;
+    }
+
+    impl PhysicalDimension for InverseVolume {
+        type BaseUnit = super::PerLitre;
+    }
+
+    impl PhysicalDimension for MassConcentration {
+        type BaseUnit = super::KilogramPerLitre;
+    }
+
+    impl PhysicalDimension for Dimensionless {
+        type BaseUnit = super::One;
+    }
+
+    /// Defines $A \cdot B = C$, together with the quotients $C / B = A$ and $C / A = B$.
+    macro_rules! dim_product {
+        ($lhs:ident * $rhs:ident = $product:ident) => {
+            impl DimMul<$rhs> for $lhs {
+                type Output = $product;
+            }
+            impl DimMul<$lhs> for $rhs {
+                type Output = $product;
+            }
+            impl DimDiv<$rhs> for $product {
+                type Output = $lhs;
+            }
+            impl DimDiv<$lhs> for $product {
+                type Output = $rhs;
+            }
+        };
+    }
+
+    dim_product!(Mass * InverseVolume = MassConcentration);
+    dim_product!(MassConcentration * Volume = Mass);
+    dim_product!(Volume * InverseVolume = Dimensionless);
+
+    /// Scaling by a dimensionless quantity keeps the dimension
+    macro_rules! dimensionless_scaling {
+        ($($dimension:ident),*) => {
+            $(
+                impl DimMul<Dimensionless> for $dimension {
+                    type Output = $dimension;
+                }
+                impl DimMul<$dimension> for Dimensionless {
+                    type Output = $dimension;
+                }
+                impl DimDiv<Dimensionless> for $dimension {
+                    type Output = $dimension;
+                }
+            )*
+        };
+    }
+
+    dimensionless_scaling!(Mass, Volume, InverseVolume, MassConcentration);
+
+    impl DimMul<Dimensionless> for Dimensionless {
+        type Output = Dimensionless;
+    }
+
+    impl<D: PhysicalDimension> DimDiv<D> for D {
+        type Output = Dimensionless;
+    }
+}
+
+/// Product of two dimensions
+pub trait DimMul<Rhs> {
+    /// Dimension of the product
+    type Output: dim::PhysicalDimension;
+}
+
+/// Quotient of two dimensions
+pub trait DimDiv<Rhs> {
+    /// Dimension of the quotient
+    type Output: dim::PhysicalDimension;
+}
+
+/// Unit of measurement for a dimension
+pub trait Unit {
+    /// Dimension measured by the unit
+    type Dimension: dim::PhysicalDimension;
+    /// Size of the unit in the base unit of the dimension
+    const BASE_FACTOR: f64;
+}
+
+/// Kilograms per litre \[kg / l\], equivalent to grams per millilitre
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KilogramPerLitre;
+
+impl Unit for KilogramPerLitre {
+    type Dimension = dim::MassConcentration;
+    const BASE_FACTOR: f64 = 1.0;
+}
+
+/// Grams per litre \[g / l\], equivalent to parts per million for dilute water solutions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GramPerLitre;
+
+impl Unit for GramPerLitre {
+    type Dimension = dim::MassConcentration;
+    const BASE_FACTOR: f64 = 1.0e-3;
+}
+
+/// Per litre \[1 / l\]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerLitre;
+
+impl Unit for PerLitre {
+    type Dimension = dim::InverseVolume;
+    const BASE_FACTOR: f64 = 1.0;
+}
+
+/// Unit of dimensionless quantities \[-\]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct One;
+
+impl Unit for One {
+    type Dimension = dim::Dimensionless;
+    const BASE_FACTOR: f64 = 1.0;
+}
+
+/// Value of dimension `D` measured in unit `U`
+pub struct Quantity<D, U> {
+    /// Value in the unit `U`
+    pub value: f32,
+    _unit: PhantomData<(D, U)>,
+}
+
+/// Quantity in the base unit of a dimension
+type BaseQuantity<D> = Quantity<D, <D as dim::PhysicalDimension>::BaseUnit>;
+
+impl<D, U> Quantity<D, U>
+where
+    D: dim::PhysicalDimension,
+    U: Unit<Dimension = D>,
+{
+    /// Validated construction, the value must be non-negative
+    pub fn new(value: f32) -> Result<Quantity<D, U>, dimension::Error> {
+        if value.is_nan() {
+            return Err(dimension::Error::ValueError("NaN value".into()));
+        }
+        if value.is_sign_negative() {
+            return Err(dimension::Error::ValueError(format!(
+                "Expected non-negative value, got: {}.",
+                value
+            )));
+        }
+        Ok(Quantity::from_value(value))
+    }
+
+    pub(crate) fn from_value(value: f32) -> Quantity<D, U> {
+        Quantity {
+            value,
+            _unit: PhantomData,
+        }
+    }
+
+    /// The same quantity in another unit of the dimension
+    pub fn convert<V: Unit<Dimension = D>>(self) -> Quantity<D, V> {
+        Quantity::from_value((f64::from(self.value) * U::BASE_FACTOR / V::BASE_FACTOR) as f32)
+    }
+
+    /// Reciprocal quantity in the base unit of the inverse dimension
+    pub fn recip(self) -> BaseQuantity<<dim::Dimensionless as DimDiv<D>>::Output>
+    where
+        dim::Dimensionless: DimDiv<D>,
+    {
+        Quantity::from_value(1.0 / self.base_value())
+    }
+
+    fn base_value(self) -> f32 {
+        (f64::from(self.value) * U::BASE_FACTOR) as f32
+    }
+}
+
+impl<D, U> Clone for Quantity<D, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D, U> Copy for Quantity<D, U> {}
+
+impl<D, U> std::fmt::Debug for Quantity<D, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Quantity")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<D, U> PartialEq for Quantity<D, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<D, U> PartialOrd for Quantity<D, U> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<D, U> dimension::Dimension for Quantity<D, U> {
+    fn value(self) -> f32 {
+        self.value
+    }
+}
+
+impl<D, U> Add for Quantity<D, U> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Quantity {
+            value: self.value + rhs.value,
+            _unit: PhantomData,
+        }
+    }
+}
+
+/// Removing more than there is fails.
+impl<D, U> Sub for Quantity<D, U>
+where
+    D: dim::PhysicalDimension,
+    U: Unit<Dimension = D>,
+{
+    type Output = Result<Self, dimension::Error>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Quantity::new(self.value - rhs.value)
+    }
+}
+
+impl<D, U> Mul<f32> for Quantity<D, U> {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self::Output {
+        Quantity {
+            value: self.value * rhs,
+            _unit: PhantomData,
+        }
+    }
+}
+
+impl<D, U> Div<f32> for Quantity<D, U> {
+    type Output = Self;
+    fn div(self, rhs: f32) -> Self::Output {
+        Quantity {
+            value: self.value / rhs,
+            _unit: PhantomData,
+        }
+    }
+}
+
+impl<D1, U1, D2, U2> Mul<Quantity<D2, U2>> for Quantity<D1, U1>
+where
+    D1: dim::PhysicalDimension + DimMul<D2>,
+    D2: dim::PhysicalDimension,
+    U1: Unit<Dimension = D1>,
+    U2: Unit<Dimension = D2>,
+{
+    type Output = BaseQuantity<<D1 as DimMul<D2>>::Output>;
+    fn mul(self, rhs: Quantity<D2, U2>) -> Self::Output {
+        Quantity::from_value(self.base_value() * rhs.base_value())
+    }
+}
+
+impl<D1, U1, D2, U2> Div<Quantity<D2, U2>> for Quantity<D1, U1>
+where
+    D1: dim::PhysicalDimension + DimDiv<D2>,
+    D2: dim::PhysicalDimension,
+    U1: Unit<Dimension = D1>,
+    U2: Unit<Dimension = D2>,
+{
+    type Output = BaseQuantity<<D1 as DimDiv<D2>>::Output>;
+    fn div(self, rhs: Quantity<D2, U2>) -> Self::Output {
+        Quantity::from_value(self.base_value() / rhs.base_value())
+    }
+}
+
+/// Density $\rho$ \[kg / l\] of a wort with a given specific gravity,
+/// taking the density of water as $1$ kg / l.
+impl From<SpecificGravity> for Quantity<dim::MassConcentration, KilogramPerLitre> {
+    fn from(gravity: SpecificGravity) -> Self {
+        Quantity::from_value(gravity.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::concentration::Concentration;
+    use crate::units::mass::{Gram, Kilogram, Mass, Pound};
+    use crate::units::volume::{Litre, UsGallon, Volume};
+    use assert_approx_eq::assert_approx_eq;
+    use std::mem::size_of;
+
+    #[test]
+    fn zero_cost() {
+        assert_eq!(size_of::<Quantity<dim::Mass, Kilogram>>(), size_of::<f32>());
+    }
+
+    #[test]
+    fn derived_dimensions() {
+        let malt = Quantity::<dim::Mass, Pound>::new(10.0).unwrap();
+        let wort = Quantity::<dim::Volume, UsGallon>::new(5.0).unwrap();
+        let concentration: Quantity<dim::MassConcentration, GramPerLitre> = (malt / wort).convert();
+        assert_approx_eq!(concentration.value, 239.65, 0.01);
+
+        let mass: Quantity<dim::Mass, Kilogram> = concentration * wort;
+        assert_approx_eq!(mass.value, 4.5359, 0.0001);
+
+        let ratio: Quantity<dim::Dimensionless, One> = wort / wort.convert::<Litre>();
+        assert_approx_eq!(ratio.value, 1.0, 1e-6);
+    }
+
+    #[test]
+    fn negative_difference_fails() {
+        let small = Quantity::<dim::Volume, Litre>::new(5.0).unwrap();
+        let large = Quantity::<dim::Volume, Litre>::new(20.0).unwrap();
+        assert_approx_eq!((large - small).unwrap().value, 15.0, 1e-6);
+        assert!((small - large).is_err());
+    }
+
+    #[test]
+    fn unit_newtype_conversions() {
+        let hops = Gram::new(7.0).unwrap();
+        let quantity: Quantity<dim::Mass, Gram> = hops.into();
+        let kilograms = Kilogram::from(quantity.convert::<Kilogram>());
+        assert_approx_eq!(kilograms.value, 0.007, 1e-7);
+    }
+
+    #[test]
+    fn wort_mass() {
+        let gravity = SpecificGravity::new(1.05).unwrap();
+        let volume = Litre::new(20.0).unwrap();
+        let mass: Kilogram = gravity * volume;
+        assert_approx_eq!(mass.value, 21.0, 1e-5);
+    }
+}
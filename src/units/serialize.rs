@@ -0,0 +1,154 @@
+//! Serde support for quantities
+//!
+//! Quantities are serialized with an explicit unit, `{ "value": 20.0, "unit": "L" }`.
+//! Deserialization accepts
+//!
+//! - the same form, where the unit may be any unit of the dimension, see [`parse`](super::parse),
+//! - a bare number in the type's own unit, e.g. `20.0`,
+//! - a string with a unit, e.g. `"5.5 gal"`.
+//!
+//! All values are validated by the type's `new` constructor.
+//!
+//! ```
+//! # use brew_calculator::units::volume::Litre;
+//! # use assert_approx_eq::assert_approx_eq;
+//! let volume: Litre = serde_json::from_str(r#"{ "value": 5.0, "unit": "gal" }"#).unwrap();
+//! assert_approx_eq!(volume.value, 18.93, 0.01);
+//! assert!(serde_json::from_str::<Litre>("-1.0").is_err());
+//! ```
+use crate::units::colour::{Ebc, Lovibond, Srm};
+use crate::units::concentration::{Plato, SpecificGravity, VolumesCO2};
+use crate::units::mass::{Gram, Kilogram, Ounce, Pound};
+use crate::units::parse::FromValueUnit;
+use crate::units::pressure::{Bar, Psi};
+use crate::units::temperature::{Celsius, Fahrenheit, Kelvin};
+use crate::units::volume::{
+    Decilitre, Hectolitre, ImperialBarrel, ImperialFluidOunce, ImperialGallon, Litre, Millilitre,
+    UsBarrel, UsFluidOunce, UsGallon,
+};
+use serde::de::Error;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Accepted serialized forms of a quantity
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Serialized {
+    Number(f32),
+    WithUnit { value: f32, unit: String },
+    Text(String),
+}
+
+/// Rejects numbers that overflowed when narrowed to `f32`, e.g. `1e39`.
+fn finite<E: Error>(value: f32) -> Result<f32, E> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(E::custom(format!(
+            "Expected a finite value, got: {}.",
+            value
+        )))
+    }
+}
+
+/// [`Serialize`] and [`Deserialize`] with a unit symbol.
+macro_rules! serde_quantity {
+    ($($name:ident: $symbol:expr;)*) => {
+        $(
+            impl Serialize for $name {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let mut state = serializer.serialize_struct(stringify!($name), 2)?;
+                    state.serialize_field("value", &self.value)?;
+                    state.serialize_field("unit", $symbol)?;
+                    state.end()
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $name {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let quantity = match Serialized::deserialize(deserializer)? {
+                        Serialized::Number(value) => $name::from_value_unit(finite(value)?, ""),
+                        Serialized::WithUnit { value, unit } => {
+                            $name::from_value_unit(finite(value)?, &unit)
+                        }
+                        Serialized::Text(text) => text.parse(),
+                    };
+                    quantity.map_err(D::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+serde_quantity!(
+    Kilogram: "kg";
+    Gram: "g";
+    Pound: "lb";
+    Ounce: "oz";
+    Litre: "L";
+    Millilitre: "ml";
+    Decilitre: "dl";
+    Hectolitre: "hl";
+    UsFluidOunce: "fl oz";
+    ImperialFluidOunce: "imp fl oz";
+    UsGallon: "gal";
+    ImperialGallon: "imp gal";
+    UsBarrel: "bbl";
+    ImperialBarrel: "imp bbl";
+    Celsius: "°C";
+    Fahrenheit: "°F";
+    Kelvin: "K";
+    SpecificGravity: "SG";
+    Plato: "°P";
+    VolumesCO2: "vol";
+    Bar: "bar";
+    Psi: "psi";
+    Srm: "SRM";
+    Ebc: "EBC";
+    Lovibond: "°L";
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::concentration::Concentration;
+    use crate::units::volume::Volume;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn serialize_with_unit() {
+        let volume = Litre::new(20.0).unwrap();
+        assert_eq!(
+            serde_json::to_string(&volume).unwrap(),
+            r#"{"value":20.0,"unit":"L"}"#
+        );
+        let gravity = SpecificGravity::new(1.05).unwrap();
+        let round_trip: SpecificGravity =
+            serde_json::from_str(&serde_json::to_string(&gravity).unwrap()).unwrap();
+        assert_eq!(round_trip, gravity);
+    }
+
+    #[test]
+    fn deserialize_forms() {
+        let volume: Litre = serde_json::from_str("20").unwrap();
+        assert_approx_eq!(volume.value, 20.0);
+        let volume: Litre = serde_json::from_str(r#""5,5 gal""#).unwrap();
+        assert_approx_eq!(volume.value, 20.82, 0.01);
+        let mash: Celsius = serde_json::from_str(r#"{"value": 152.0, "unit": "°F"}"#).unwrap();
+        assert_approx_eq!(mash.value, 66.67, 0.01);
+        let og: Plato = serde_json::from_str(r#"{"value": 1.048, "unit": "SG"}"#).unwrap();
+        assert_approx_eq!(og.value, 11.9, 0.1);
+        let crystal: Lovibond = serde_json::from_str(r#"{"value": 120.0, "unit": "EBC"}"#).unwrap();
+        assert_approx_eq!(crystal.value, 45.53, 0.01);
+    }
+
+    #[test]
+    fn deserialize_validates() {
+        assert!(serde_json::from_str::<Kilogram>("-0.5").is_err());
+        assert!(serde_json::from_str::<Celsius>(r#"{"value": -300.0, "unit": "°C"}"#).is_err());
+        assert!(serde_json::from_str::<Litre>(r#"{"value": 1.0, "unit": "kg"}"#).is_err());
+        assert!(serde_json::from_str::<Litre>(r#""NaN l""#).is_err());
+        assert!(serde_json::from_str::<Litre>("1e39").is_err());
+        assert!(serde_json::from_str::<Kilogram>(r#"{"value": 1e39, "unit": "kg"}"#).is_err());
+    }
+}
@@ -0,0 +1,398 @@
+//! Temperature units
+//!
+//! Absolute temperatures ([`Celsius`], [`Fahrenheit`], [`Kelvin`]) are kept apart from
+//! temperature differences ([`TemperatureDifference`]).
+//! The difference of two temperatures is a temperature difference,
+//! which can be added to a temperature, while adding two temperatures will not compile.
+//! Adding or subtracting a difference validates the result like `new`, so it gives a
+//! `Result`, as the result may be below absolute zero.
+use crate::units::dimension;
+use std::f32;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Absolute zero in degrees Celsius
+const ABSOLUTE_ZERO_CELSIUS: f32 = -273.15;
+/// Size of a degree Fahrenheit in kelvin
+const FAHRENHEIT_DEGREE: f32 = 5.0 / 9.0;
+/// Freezing point of water in degrees Fahrenheit
+const FREEZING_POINT_FAHRENHEIT: f32 = 32.0;
+
+/// Absolute temperature
+pub trait Temperature: dimension::Dimension + Sized {
+    /// Validated construction, the value must be finite and not below absolute zero
+    fn new(value: f32) -> Result<Self, dimension::Error>;
+    /// Temperature in kelvin
+    fn to_kelvin(self) -> f32;
+}
+
+fn validate(value: f32, kelvin: f32) -> Result<(), dimension::Error> {
+    validate_finite(value)?;
+    if kelvin < 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected temperature above absolute zero, got: {}.",
+            value
+        )));
+    }
+    Ok(())
+}
+
+fn validate_finite(value: f32) -> Result<(), dimension::Error> {
+    if value.is_nan() {
+        return Err(dimension::Error::ValueError("NaN value".into()));
+    }
+    if value.is_infinite() {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected finite value, got: {}.",
+            value
+        )));
+    }
+    Ok(())
+}
+
+/// Degrees Celsius \[°C\]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Celsius {
+    /// Temperature in degrees Celsius
+    pub value: f32,
+    _secret: (),
+}
+
+impl Temperature for Celsius {
+    fn new(value: f32) -> Result<Celsius, dimension::Error> {
+        validate(value, value - ABSOLUTE_ZERO_CELSIUS)?;
+        Ok(Celsius { value, _secret: () })
+    }
+
+    fn to_kelvin(self) -> f32 {
+        self.value - ABSOLUTE_ZERO_CELSIUS
+    }
+}
+
+/// Degrees Fahrenheit \[°F\]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Fahrenheit {
+    /// Temperature in degrees Fahrenheit
+    pub value: f32,
+    _secret: (),
+}
+
+impl Temperature for Fahrenheit {
+    fn new(value: f32) -> Result<Fahrenheit, dimension::Error> {
+        validate(value, fahrenheit_to_kelvin(value))?;
+        Ok(Fahrenheit { value, _secret: () })
+    }
+
+    fn to_kelvin(self) -> f32 {
+        fahrenheit_to_kelvin(self.value)
+    }
+}
+
+fn fahrenheit_to_kelvin(value: f32) -> f32 {
+    (value - FREEZING_POINT_FAHRENHEIT) * FAHRENHEIT_DEGREE - ABSOLUTE_ZERO_CELSIUS
+}
+
+/// Kelvin \[K\]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Kelvin {
+    /// Temperature in kelvin
+    pub value: f32,
+    _secret: (),
+}
+
+impl Temperature for Kelvin {
+    fn new(value: f32) -> Result<Kelvin, dimension::Error> {
+        validate(value, value)?;
+        Ok(Kelvin { value, _secret: () })
+    }
+
+    fn to_kelvin(self) -> f32 {
+        self.value
+    }
+}
+
+impl From<Celsius> for Fahrenheit {
+    fn from(temperature: Celsius) -> Fahrenheit {
+        Fahrenheit {
+            value: temperature.value / FAHRENHEIT_DEGREE + FREEZING_POINT_FAHRENHEIT,
+            _secret: (),
+        }
+    }
+}
+
+impl From<Celsius> for Kelvin {
+    fn from(temperature: Celsius) -> Kelvin {
+        Kelvin {
+            value: temperature.to_kelvin(),
+            _secret: (),
+        }
+    }
+}
+
+impl From<Fahrenheit> for Celsius {
+    fn from(temperature: Fahrenheit) -> Celsius {
+        Celsius {
+            value: (temperature.value - FREEZING_POINT_FAHRENHEIT) * FAHRENHEIT_DEGREE,
+            _secret: (),
+        }
+    }
+}
+
+impl From<Fahrenheit> for Kelvin {
+    fn from(temperature: Fahrenheit) -> Kelvin {
+        Kelvin {
+            value: temperature.to_kelvin(),
+            _secret: (),
+        }
+    }
+}
+
+impl From<Kelvin> for Celsius {
+    fn from(temperature: Kelvin) -> Celsius {
+        Celsius {
+            value: temperature.value + ABSOLUTE_ZERO_CELSIUS,
+            _secret: (),
+        }
+    }
+}
+
+impl From<Kelvin> for Fahrenheit {
+    fn from(temperature: Kelvin) -> Fahrenheit {
+        Celsius::from(temperature).into()
+    }
+}
+
+impl dimension::Dimension for Celsius {
+    fn value(self) -> f32 {
+        self.value
+    }
+}
+
+impl dimension::Dimension for Fahrenheit {
+    fn value(self) -> f32 {
+        self.value
+    }
+}
+
+impl dimension::Dimension for Kelvin {
+    fn value(self) -> f32 {
+        self.value
+    }
+}
+
+/// Temperature difference \[K\], equivalently \[°C\]
+///
+/// Unlike absolute temperatures it may be negative.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct TemperatureDifference {
+    /// Temperature difference in kelvin
+    pub value: f32,
+    _secret: (),
+}
+
+impl TemperatureDifference {
+    /// Validated construction from a difference in kelvin, the value must be finite
+    pub fn new(value: f32) -> Result<TemperatureDifference, dimension::Error> {
+        validate_finite(value)?;
+        Ok(TemperatureDifference { value, _secret: () })
+    }
+
+    /// Validated construction from a difference in degrees Fahrenheit
+    pub fn from_fahrenheit(value: f32) -> Result<TemperatureDifference, dimension::Error> {
+        TemperatureDifference::new(value * FAHRENHEIT_DEGREE)
+    }
+
+    /// Temperature difference in degrees Fahrenheit
+    pub fn fahrenheit(self) -> f32 {
+        self.value / FAHRENHEIT_DEGREE
+    }
+}
+
+impl dimension::Dimension for TemperatureDifference {
+    fn value(self) -> f32 {
+        self.value
+    }
+}
+
+impl Sub for Celsius {
+    type Output = TemperatureDifference;
+    fn sub(self, rhs: Celsius) -> Self::Output {
+        TemperatureDifference {
+            value: self.value - rhs.value,
+            _secret: (),
+        }
+    }
+}
+
+impl Add<TemperatureDifference> for Celsius {
+    type Output = Result<Self, dimension::Error>;
+    fn add(self, rhs: TemperatureDifference) -> Self::Output {
+        Celsius::new(self.value + rhs.value)
+    }
+}
+
+impl Sub<TemperatureDifference> for Celsius {
+    type Output = Result<Self, dimension::Error>;
+    fn sub(self, rhs: TemperatureDifference) -> Self::Output {
+        Celsius::new(self.value - rhs.value)
+    }
+}
+
+impl Sub for Kelvin {
+    type Output = TemperatureDifference;
+    fn sub(self, rhs: Kelvin) -> Self::Output {
+        TemperatureDifference {
+            value: self.value - rhs.value,
+            _secret: (),
+        }
+    }
+}
+
+impl Add<TemperatureDifference> for Kelvin {
+    type Output = Result<Self, dimension::Error>;
+    fn add(self, rhs: TemperatureDifference) -> Self::Output {
+        Kelvin::new(self.value + rhs.value)
+    }
+}
+
+impl Sub<TemperatureDifference> for Kelvin {
+    type Output = Result<Self, dimension::Error>;
+    fn sub(self, rhs: TemperatureDifference) -> Self::Output {
+        Kelvin::new(self.value - rhs.value)
+    }
+}
+
+impl Sub for Fahrenheit {
+    type Output = TemperatureDifference;
+    fn sub(self, rhs: Fahrenheit) -> Self::Output {
+        TemperatureDifference {
+            value: (self.value - rhs.value) * FAHRENHEIT_DEGREE,
+            _secret: (),
+        }
+    }
+}
+
+impl Add<TemperatureDifference> for Fahrenheit {
+    type Output = Result<Self, dimension::Error>;
+    fn add(self, rhs: TemperatureDifference) -> Self::Output {
+        Fahrenheit::new(self.value + rhs.fahrenheit())
+    }
+}
+
+impl Sub<TemperatureDifference> for Fahrenheit {
+    type Output = Result<Self, dimension::Error>;
+    fn sub(self, rhs: TemperatureDifference) -> Self::Output {
+        Fahrenheit::new(self.value - rhs.fahrenheit())
+    }
+}
+
+impl Add for TemperatureDifference {
+    type Output = Self;
+    fn add(self, rhs: TemperatureDifference) -> Self::Output {
+        TemperatureDifference {
+            value: self.value + rhs.value,
+            _secret: (),
+        }
+    }
+}
+
+impl Sub for TemperatureDifference {
+    type Output = Self;
+    fn sub(self, rhs: TemperatureDifference) -> Self::Output {
+        TemperatureDifference {
+            value: self.value - rhs.value,
+            _secret: (),
+        }
+    }
+}
+
+impl Neg for TemperatureDifference {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        TemperatureDifference {
+            value: -self.value,
+            _secret: (),
+        }
+    }
+}
+
+impl Mul<f32> for TemperatureDifference {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self::Output {
+        TemperatureDifference {
+            value: self.value * rhs,
+            _secret: (),
+        }
+    }
+}
+
+impl Div<f32> for TemperatureDifference {
+    type Output = Self;
+    fn div(self, rhs: f32) -> Self::Output {
+        TemperatureDifference {
+            value: self.value / rhs,
+            _secret: (),
+        }
+    }
+}
+
+impl Div for TemperatureDifference {
+    type Output = f32;
+    fn div(self, rhs: TemperatureDifference) -> Self::Output {
+        self.value / rhs.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn conversions() {
+        let boiling = Celsius::new(100.0).unwrap();
+        assert_approx_eq!(Fahrenheit::from(boiling).value, 212.0, 0.001);
+        assert_approx_eq!(Kelvin::from(boiling).value, 373.15, 0.001);
+
+        let mash = Fahrenheit::new(152.0).unwrap();
+        assert_approx_eq!(Celsius::from(mash).value, 66.667, 0.001);
+        let round_trip: Fahrenheit = Celsius::from(Kelvin::from(mash)).into();
+        assert_approx_eq!(round_trip.value, 152.0, 0.001);
+    }
+
+    #[test]
+    fn below_absolute_zero() {
+        assert!(Celsius::new(-273.15).is_ok());
+        assert!(Celsius::new(-274.0).is_err());
+        assert!(Fahrenheit::new(-460.0).is_err());
+        assert!(Kelvin::new(-0.1).is_err());
+        assert!(Kelvin::new(f32::NAN).is_err());
+        assert!(Celsius::new(f32::INFINITY).is_err());
+        assert!(TemperatureDifference::new(f32::NEG_INFINITY).is_err());
+    }
+
+    #[test]
+    fn difference_below_absolute_zero() {
+        let room = Celsius::new(20.0).unwrap();
+        let difference = TemperatureDifference::new(400.0).unwrap();
+        assert!((room - difference).is_err());
+        assert!((Kelvin::from(room) - difference).is_err());
+        assert!((Fahrenheit::from(room) - difference).is_err());
+        assert!((room + -difference).is_err());
+        let cooled = (Fahrenheit::from(room) - difference * 0.05).unwrap();
+        assert_approx_eq!(Celsius::from(cooled).value, 0.0, 0.001);
+    }
+
+    #[test]
+    fn difference_ops() {
+        let mash = Celsius::new(66.0).unwrap();
+        let grain = Celsius::new(20.0).unwrap();
+        let difference = mash - grain;
+        assert_approx_eq!(difference.value, 46.0, 0.001);
+        assert_approx_eq!((grain + difference).unwrap().value, 66.0, 0.001);
+        assert_approx_eq!(difference.fahrenheit(), 82.8, 0.001);
+
+        let fahrenheit_difference = Fahrenheit::from(mash) - Fahrenheit::from(grain);
+        assert_approx_eq!(fahrenheit_difference.value, 46.0, 0.001);
+        assert_approx_eq!(difference / (difference * 0.5), 2.0, 0.001);
+    }
+}
@@ -1,65 +1,153 @@
+//! Volume units
+//!
+//! Metric, US customary and imperial volume units, with conversions between all of them.
+//! The barrels are the beer barrels used by commercial brewers.
 use crate::units::dimension;
-use std::f32;
-use std::ops::{Add, Mul};
+use crate::units::quantity::dim;
 
+/// Volume quantity
 pub trait Volume: dimension::Dimension + Sized {
+    /// Validated construction, the value must be non-negative
     fn new(value: f32) -> Result<Self, dimension::Error>;
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct Litre {
-    pub value: f32,
-    _secret: (),
-}
+/// Litres per US gallon, exactly $231$ cubic inches
+const LITRES_PER_US_GALLON: f64 = 3.785_411_784;
+/// Litres per US gallon, for compound units such as points per pound per gallon
+pub(crate) const LITRES_PER_US_GALLON_F32: f32 = LITRES_PER_US_GALLON as f32;
+/// Litres per imperial gallon
+const LITRES_PER_IMPERIAL_GALLON: f64 = 4.546_09;
 
-impl dimension::Dimension for Litre {
-    fn value(self) -> f32 {
-        self.value
-    }
-}
+linear_unit!(
+    /// Litre \[l\]
+    Litre: Volume, 1.0
+);
 
-impl Volume for Litre {
-    fn new(value: f32) -> Result<Litre, dimension::Error> {
-        if value.is_nan() {
-            return Err(dimension::Error::ValueError("NaN value".into()));
-        }
-        if value.is_sign_negative() {
-            return Err(dimension::Error::ValueError(format!(
-                "Expected non-negative value, got: {}.",
-                value.to_string()
-            )));
-        }
-        Ok(Litre { value, _secret: () })
-    }
-}
+linear_unit!(
+    /// Millilitre \[ml\]
+    Millilitre: Volume, 1.0e-3
+);
 
-impl Mul<f32> for Litre {
-    type Output = Self;
-    fn mul(self, rhs: f32) -> Self::Output {
-        Self {
-            value: self.value * rhs,
-            _secret: (),
-        }
-    }
-}
+linear_unit!(
+    /// Decilitre \[dl\]
+    Decilitre: Volume, 0.1
+);
 
-impl Add for Litre {
-    type Output = Self;
-    fn add(self, rhs: Litre) -> Self::Output {
-        Litre {
-            value: self.value + rhs.value,
-            _secret: (),
-        }
-    }
-}
+linear_unit!(
+    /// Hectolitre \[hl\]
+    Hectolitre: Volume, 100.0
+);
+
+linear_unit!(
+    /// US fluid ounce \[fl oz\], $1/128$ US gal
+    UsFluidOunce: Volume, LITRES_PER_US_GALLON / 128.0
+);
+
+linear_unit!(
+    /// Imperial fluid ounce \[fl oz\], $1/160$ imp gal
+    ImperialFluidOunce: Volume, LITRES_PER_IMPERIAL_GALLON / 160.0
+);
+
+linear_unit!(
+    /// US gallon \[gal\]
+    UsGallon: Volume, LITRES_PER_US_GALLON
+);
+
+linear_unit!(
+    /// Imperial gallon \[gal\]
+    ImperialGallon: Volume, LITRES_PER_IMPERIAL_GALLON
+);
+
+linear_unit!(
+    /// US beer barrel \[bbl\], $31$ US gal
+    UsBarrel: Volume, 31.0 * LITRES_PER_US_GALLON
+);
+
+linear_unit!(
+    /// UK beer barrel \[bbl\], $36$ imp gal
+    ImperialBarrel: Volume, 36.0 * LITRES_PER_IMPERIAL_GALLON
+);
+
+unit_conversions!(
+    Litre,
+    Millilitre,
+    Decilitre,
+    Hectolitre,
+    UsFluidOunce,
+    ImperialFluidOunce,
+    UsGallon,
+    ImperialGallon,
+    UsBarrel,
+    ImperialBarrel
+);
+quantity_units!(
+    dim::Volume: Litre,
+    Millilitre,
+    Decilitre,
+    Hectolitre,
+    UsFluidOunce,
+    ImperialFluidOunce,
+    UsGallon,
+    ImperialGallon,
+    UsBarrel,
+    ImperialBarrel
+);
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use assert_approx_eq::assert_approx_eq;
     #[test]
     fn add_ops_test() {
         let vol_1 = Litre::new(1.1).unwrap();
         let vol_2 = Litre::new(2.3).unwrap();
         assert_eq!((vol_1 + vol_2).value, 3.4);
     }
+
+    #[test]
+    fn sub_div_ops_test() {
+        let vol_1 = Litre::new(20.0).unwrap();
+        let vol_2 = Litre::new(5.0).unwrap();
+        assert_eq!((vol_1 - vol_2).unwrap().value, 15.0);
+        assert_eq!((vol_1 / 4.0).value, 5.0);
+        assert_eq!(vol_1 / vol_2, 4.0);
+    }
+
+    #[test]
+    fn negative_difference_fails() {
+        let vol_1 = Litre::new(5.0).unwrap();
+        let vol_2 = Litre::new(20.0).unwrap();
+        assert!((vol_1 - vol_2).is_err());
+    }
+
+    #[test]
+    fn conversions() {
+        let barrel = UsBarrel::new(1.0).unwrap();
+        assert_approx_eq!(Litre::from(barrel).value, 117.347_77, 1e-4);
+        assert_approx_eq!(UsGallon::from(barrel).value, 31.0, 1e-5);
+        assert_approx_eq!(
+            Hectolitre::from(ImperialBarrel::new(1.0).unwrap()).value,
+            1.636_592,
+            1e-6
+        );
+        assert_approx_eq!(
+            ImperialFluidOunce::from(ImperialGallon::new(1.0).unwrap()).value,
+            160.0,
+            1e-4
+        );
+        assert_approx_eq!(
+            Millilitre::from(UsFluidOunce::new(12.0).unwrap()).value,
+            354.882,
+            1e-3
+        );
+        assert_approx_eq!(Decilitre::from(Litre::new(0.33).unwrap()).value, 3.3, 1e-6);
+    }
+
+    #[test]
+    fn round_trip() {
+        let volume = Litre::new(23.0).unwrap();
+        let round_trip: Litre =
+            UsBarrel::from(Millilitre::from(UsGallon::from(Hectolitre::from(volume)))).into();
+        assert_approx_eq!(round_trip.value, volume.value, 1e-5);
+    }
 }
@@ -0,0 +1,133 @@
+//! Yeast pitching
+//!
+//! - $r$ \[$10^6$ cells / (ml °P)\]: Pitch rate, see [`BeerType`],
+//! - $V$ \[l\]: Wort volume,
+//! - $OG$ \[°P\]: Original gravity,
+//! - $N$ \[$10^9$ cells\]: Cell count.
+
+use crate::units::concentration::Plato;
+use crate::units::dimension;
+use crate::units::volume::Litre;
+use crate::units::Percent;
+use serde::{Deserialize, Serialize};
+
+/// Kind of fermentation, which sets the pitch rate
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+pub enum BeerType {
+    /// Top fermented at ale temperatures
+    Ale,
+    /// Bottom fermented at lager temperatures
+    Lager,
+    /// Ale yeast fermented cold or lager yeast fermented warm
+    Hybrid,
+}
+
+impl BeerType {
+    /// Standard pitch rate $r$ \[$10^6$ cells / (ml °P)\]
+    pub fn pitch_rate(self) -> f32 {
+        match self {
+            BeerType::Ale => 0.75,
+            BeerType::Lager => 1.5,
+            BeerType::Hybrid => 1.0,
+        }
+    }
+}
+
+/// Packaged yeast, a pack, vial or sachet
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub struct YeastPack {
+    /// Cells in a fresh pack \[$10^9$ cells\]
+    pub cells: f32,
+    /// Share of the cells still alive
+    pub viability: Percent,
+}
+
+impl YeastPack {
+    /// Living cells in the pack \[$10^9$ cells\]
+    pub fn viable_cells(&self) -> f32 {
+        self.cells * self.viability / 100.0
+    }
+}
+
+/// Cells to pitch $N$ \[$10^9$ cells\]
+///
+/// $$
+///     N = r \cdot V \cdot OG,
+/// $$
+/// where the factor $1000$ ml / l cancels against counting in billions.
+///
+/// ```
+/// # use brew_calculator::yeast::{required_cells, BeerType};
+/// # use brew_calculator::units::concentration::{Concentration, Plato};
+/// # use brew_calculator::units::volume::{Litre, Volume};
+/// # use assert_approx_eq::assert_approx_eq;
+/// let cells = required_cells(
+///     Plato::new(12.0).unwrap(),
+///     Litre::new(20.0).unwrap(),
+///     BeerType::Ale,
+/// );
+/// assert_approx_eq!(cells, 180.0, 0.01);
+/// ```
+pub fn required_cells(og: Plato, volume: Litre, beer_type: BeerType) -> f32 {
+    beer_type.pitch_rate() * volume.value * og.value
+}
+
+/// Packs needed to pitch at least the required cells
+///
+/// ```
+/// # use brew_calculator::yeast::{packs_needed, YeastPack};
+/// let pack = YeastPack {
+///     cells: 100.0,
+///     viability: 80.0,
+/// };
+/// assert_eq!(packs_needed(180.0, &pack).unwrap(), 3);
+/// ```
+pub fn packs_needed(required_cells: f32, pack: &YeastPack) -> Result<u32, dimension::Error> {
+    let viable_cells = pack.viable_cells();
+    if viable_cells.is_nan() || viable_cells <= 0.0 {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected viable cells in the pack, got: {}.",
+            viable_cells
+        )));
+    }
+    if required_cells.is_nan() || required_cells.is_sign_negative() {
+        return Err(dimension::Error::ValueError(format!(
+            "Expected non-negative cell count, got: {}.",
+            required_cells
+        )));
+    }
+    Ok((required_cells / viable_cells).ceil() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::concentration::Concentration;
+    use crate::units::volume::Volume;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_required_cells() {
+        let og = Plato::new(12.0).unwrap();
+        let volume = Litre::new(20.0).unwrap();
+        assert_approx_eq!(required_cells(og, volume, BeerType::Lager), 360.0, 0.01);
+        assert_approx_eq!(required_cells(og, volume, BeerType::Hybrid), 240.0, 0.01);
+    }
+
+    #[test]
+    fn test_packs_needed() {
+        let pack = YeastPack {
+            cells: 100.0,
+            viability: 90.0,
+        };
+        assert_eq!(packs_needed(360.0, &pack).unwrap(), 4);
+        assert_eq!(packs_needed(90.0, &pack).unwrap(), 1);
+        assert_eq!(packs_needed(0.0, &pack).unwrap(), 0);
+        let dead = YeastPack {
+            cells: 100.0,
+            viability: 0.0,
+        };
+        assert!(packs_needed(180.0, &dead).is_err());
+        assert!(packs_needed(-1.0, &pack).is_err());
+    }
+}